@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// Running counters an achievement can be judged against, gathered by the app
+/// whenever something worth checking happens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Progress {
+    pub links_restored: u64,
+    pub net_worth: f64,
+    pub foundry_cores_owned: u32,
+    /// Best sale price relative to the long moving average at the time.
+    pub best_sale_ratio: f64,
+}
+
+/// Milestones, persisted by name in the save file once unlocked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AchievementId {
+    FirstLink,
+    HundredLinks,
+    Millionaire,
+    FoundryCore,
+    SpikeSeller,
+}
+
+impl AchievementId {
+    pub const ALL: [AchievementId; 5] = [
+        AchievementId::FirstLink,
+        AchievementId::HundredLinks,
+        AchievementId::Millionaire,
+        AchievementId::FoundryCore,
+        AchievementId::SpikeSeller,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            AchievementId::FirstLink => "First Link Restored",
+            AchievementId::HundredLinks => "Centurion",
+            AchievementId::Millionaire => "Millionaire",
+            AchievementId::FoundryCore => "Forged in the Core",
+            AchievementId::SpikeSeller => "Sell the Spike",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            AchievementId::FirstLink => "Restore your first link.",
+            AchievementId::HundredLinks => "Restore 100 links.",
+            AchievementId::Millionaire => "Reach 1,000,000₵ net worth.",
+            AchievementId::FoundryCore => "Buy a Foundry Core.",
+            AchievementId::SpikeSeller => "Sell at twice the long moving average.",
+        }
+    }
+
+    pub fn is_met(self, progress: &Progress) -> bool {
+        match self {
+            AchievementId::FirstLink => progress.links_restored >= 1,
+            AchievementId::HundredLinks => progress.links_restored >= 100,
+            AchievementId::Millionaire => progress.net_worth >= 1_000_000.0,
+            AchievementId::FoundryCore => progress.foundry_cores_owned > 0,
+            AchievementId::SpikeSeller => progress.best_sale_ratio >= 2.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn met(id: AchievementId, progress: Progress) -> bool {
+        id.is_met(&progress)
+    }
+
+    #[test]
+    fn first_link_needs_one_restore() {
+        assert!(!met(AchievementId::FirstLink, Progress::default()));
+        let progress = Progress {
+            links_restored: 1,
+            ..Progress::default()
+        };
+        assert!(met(AchievementId::FirstLink, progress));
+    }
+
+    #[test]
+    fn hundred_links_unlocks_at_exactly_one_hundred() {
+        let at = |links_restored| Progress {
+            links_restored,
+            ..Progress::default()
+        };
+        assert!(!met(AchievementId::HundredLinks, at(99)));
+        assert!(met(AchievementId::HundredLinks, at(100)));
+    }
+
+    #[test]
+    fn millionaire_unlocks_at_a_million_net_worth() {
+        let at = |net_worth| Progress {
+            net_worth,
+            ..Progress::default()
+        };
+        assert!(!met(AchievementId::Millionaire, at(999_999.99)));
+        assert!(met(AchievementId::Millionaire, at(1_000_000.0)));
+    }
+
+    #[test]
+    fn foundry_core_needs_one_owned() {
+        assert!(!met(AchievementId::FoundryCore, Progress::default()));
+        let progress = Progress {
+            foundry_cores_owned: 1,
+            ..Progress::default()
+        };
+        assert!(met(AchievementId::FoundryCore, progress));
+    }
+
+    #[test]
+    fn spike_seller_unlocks_at_twice_the_average() {
+        let at = |best_sale_ratio| Progress {
+            best_sale_ratio,
+            ..Progress::default()
+        };
+        assert!(!met(AchievementId::SpikeSeller, at(1.99)));
+        assert!(met(AchievementId::SpikeSeller, at(2.0)));
+    }
+}
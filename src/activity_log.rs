@@ -0,0 +1,83 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::Local;
+
+pub const LOG_FILE: &str = "blockgrave.log";
+/// Once the log passes this size it is moved to `<file>.1` and restarted.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Timestamped copy of the message feed. Lines are buffered and flushed on an
+/// interval so ticks never wait on disk.
+pub struct ActivityLog {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    written: u64,
+    last_flush: Instant,
+}
+
+impl ActivityLog {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (writer, written) = open_append(&path)?;
+        Ok(Self {
+            path,
+            writer,
+            written,
+            last_flush: Instant::now(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&mut self, message: &str) -> Result<()> {
+        if self.written >= MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        let line = format!("{} {}\n", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+        self.writer
+            .write_all(line.as_bytes())
+            .with_context(|| format!("writing {}", self.path.display()))?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    /// Flushes buffered lines if enough time has passed since the last flush.
+    pub fn maybe_flush(&mut self) -> Result<()> {
+        if self.last_flush.elapsed() < FLUSH_INTERVAL {
+            return Ok(());
+        }
+        self.last_flush = Instant::now();
+        self.writer
+            .flush()
+            .with_context(|| format!("flushing {}", self.path.display()))
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&self.path, &rotated)
+            .with_context(|| format!("rotating {}", self.path.display()))?;
+        let (writer, written) = open_append(&self.path)?;
+        self.writer = writer;
+        self.written = written;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<(BufWriter<File>, u64)> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    let written = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+    Ok((BufWriter::new(file), written))
+}
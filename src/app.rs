@@ -1,29 +1,89 @@
-use std::collections::VecDeque;
+mod bank;
+mod clock;
+mod format;
+mod hashpower;
+mod ledger;
+mod mining;
+mod save;
+mod ticker;
+
+use std::cell::Cell;
+use std::collections::{HashSet, VecDeque};
 use std::fs;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow};
-use chrono::{DateTime, Local, Utc};
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use nanoid::nanoid;
+use arboard::Clipboard;
+use chrono::NaiveDate;
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use rand::SeedableRng;
 use rand::rngs::StdRng;
-use rand::seq::SliceRandom;
-use rand::{Rng, SeedableRng};
-use rand_distr::{Distribution, LogNormal};
+use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 
+use crate::achievements::{AchievementId, Progress};
+use crate::activity_log::{ActivityLog, LOG_FILE};
+use crate::config::{CONFIG_FILE, GameConfig};
+use crate::daily::{
+    self, DAILY_GOAL_NET_WORTH, DAILY_TIME_LIMIT_SECS, DailyChallenge, DailyOutcome,
+};
+use crate::goal::{Goal, GoalCompletion, GoalMetric};
+use crate::keymap::{Action, KEYMAP_FILE, Keymap, Scope};
+use crate::preset::Preset;
+use crate::snapshot::SNAPSHOT_FILE;
+use crate::theme::ThemeKind;
+
+pub use bank::{
+    AUTO_SELL_CAP_STEP, Asset, DEFAULT_EXCHANGE_SPREAD, DEFAULT_MARKET_DEPTH,
+    EARLY_UNSTAKE_PENALTY, EXCHANGE_FLAT_FEE, LOAN_AUTO_REPAY_SHARE, LOAN_RATE_PER_HOUR, LOAN_STEP,
+    OrderSide, STAKE_STEP, STAKING_RATE_PER_HOUR,
+};
+use bank::{
+    AssetQuotes, BankState, LOAN_BASE_LIMIT, LOAN_LIMIT_PER_POWER, LimitOrder, LimitOrderEvent,
+    MarketFill, STAKE_LOCK_SECS,
+};
+pub use clock::{Clock, SimulatedClock, SystemClock};
+pub use format::{format_duration, format_eta, format_price, format_price_delta, format_relings};
+use hashpower::{FOUNDRY_CORE_TIER, HashpowerState, OVERCLOCK_DURATION_SECS, OVERCLOCK_MULTIPLIER};
+pub use hashpower::{HashpowerView, Overclock, TIER_UNLOCK_OWNED};
+pub use ledger::LedgerEntry;
+use ledger::LedgerState;
+pub use mining::{
+    ActiveJob, CONTRACT_TTL_MAX_SECS, CompletionFlash, JobRarity, LinkletStatus, MiningJob,
+    WorkStrategy,
+};
+use mining::{MiningState, generate_link_id};
+use save::{
+    EXPORT_FILE, EXPORT_PREFIX, LoadSource, SAVE_BACKUP_FILE, SAVE_FILE, SaveData, decode_export,
+    encode_export, read_save_file, write_save_atomically,
+};
+pub use ticker::{
+    ChartMode, ChartScale, DEFAULT_PRICE_HISTORY, LONG_MA_WINDOW, MaCrossover, SHORT_MA_WINDOW,
+};
+use ticker::{PriceAlert, SHARD_INITIAL_PRICE, TickerState};
+
 const MAX_MESSAGES: usize = 5;
-const JOB_POOL_SIZE: usize = 4;
-const NANO_ALPHABET: &[char] = &[
-    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
-    'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
-];
-const SAVE_FILE: &str = "blockgrave-save.json";
-const PRICE_UPDATE_MIN_SECS: f64 = 5.0;
-const PRICE_UPDATE_MAX_SECS: f64 = 15.0;
-const EXCHANGE_BUY_MULTIPLIER: f64 = 1.01;
-const EXCHANGE_SELL_MULTIPLIER: f64 = 0.99;
+const LIMIT_PRICE_STEP: f64 = 0.5;
+/// Units bought by the buy ×10 key.
+const BULK_PURCHASE_COUNT: u32 = 10;
+/// Purchases costing more than this share of the credit balance ask for confirmation first.
+const PURCHASE_CONFIRM_FRACTION: f64 = 0.25;
+/// How long a trade or purchase stays undoable before the market moves on.
+const UNDO_WINDOW_SECS: f64 = 5.0;
+/// Smallest position, in credits, that can move the market on purpose.
+pub const MANIPULATION_MIN_VALUE: f64 = 1_000.0;
+/// Ceiling on the fractional price swing a single pump or dump can cause.
+const MANIPULATION_MAX_SHOCK: f64 = 0.3;
+/// Position value that produces half of `MANIPULATION_MAX_SHOCK`.
+const MANIPULATION_HALF_VALUE: f64 = 10_000.0;
+pub const MANIPULATION_COOLDOWN_SECS: f64 = 300.0;
+const DEFAULT_AUTOSAVE_SECS: u64 = 60;
+const AUTOSAVE_PRESETS_SECS: [u64; 5] = [0, 30, 60, 120, 300];
+pub const PRESTIGE_THRESHOLD: f64 = 10_000.0;
+const PRESTIGE_GAIN_SCALE: f64 = 0.25;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PaneFocus {
@@ -58,6 +118,15 @@ pub enum PauseMenuItem {
     Resume,
     Save,
     Load,
+    Export,
+    Import,
+    Autosave,
+    Theme,
+    Glyphs,
+    FocusPause,
+    Achievements,
+    Prestige,
+    NewGame,
     Quit,
 }
 
@@ -67,22 +136,74 @@ impl PauseMenuItem {
             PauseMenuItem::Resume => "Resume",
             PauseMenuItem::Save => "Save",
             PauseMenuItem::Load => "Load",
+            PauseMenuItem::Export => "Export code",
+            PauseMenuItem::Import => "Import code",
+            PauseMenuItem::Autosave => "Autosave",
+            PauseMenuItem::Theme => "Theme",
+            PauseMenuItem::Glyphs => "Grid glyphs",
+            PauseMenuItem::FocusPause => "Pause when unfocused",
+            PauseMenuItem::Achievements => "Achievements",
+            PauseMenuItem::Prestige => "Prestige",
+            PauseMenuItem::NewGame => "New game",
             PauseMenuItem::Quit => "Exit",
         }
     }
 }
 
+/// How finely the Active Link grid shows each linklet's progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GridGlyphs {
+    /// Five shade blocks; safe on terminals with limited glyph support.
+    #[default]
+    Coarse,
+    /// Eighth-height blocks for smoother progress.
+    Fine,
+}
+
+impl GridGlyphs {
+    pub fn label(self) -> &'static str {
+        match self {
+            GridGlyphs::Coarse => "Coarse",
+            GridGlyphs::Fine => "Fine",
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            GridGlyphs::Coarse => GridGlyphs::Fine,
+            GridGlyphs::Fine => GridGlyphs::Coarse,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseConfirm {
+    Prestige,
+    SaveBeforeExit,
+    SaveBeforeNewGame,
+}
+
 #[derive(Debug, Default)]
 pub struct PauseMenuState {
     selected: usize,
     status: Option<String>,
+    confirm: Option<PauseConfirm>,
 }
 
 impl PauseMenuState {
-    const OPTIONS: [PauseMenuItem; 4] = [
+    const OPTIONS: [PauseMenuItem; 13] = [
         PauseMenuItem::Resume,
         PauseMenuItem::Save,
         PauseMenuItem::Load,
+        PauseMenuItem::Export,
+        PauseMenuItem::Import,
+        PauseMenuItem::Autosave,
+        PauseMenuItem::Theme,
+        PauseMenuItem::Glyphs,
+        PauseMenuItem::FocusPause,
+        PauseMenuItem::Achievements,
+        PauseMenuItem::Prestige,
+        PauseMenuItem::NewGame,
         PauseMenuItem::Quit,
     ];
 
@@ -117,95 +238,678 @@ impl PauseMenuState {
     pub fn set_status(&mut self, status: Option<String>) {
         self.status = status;
     }
+
+    pub fn confirm(&self) -> Option<PauseConfirm> {
+        self.confirm
+    }
 }
 
 pub struct App {
     pub focus: PaneFocus,
+    pub zoom: Option<PaneFocus>,
+    pub config: GameConfig,
+    pub keymap: Keymap,
+    pub theme: ThemeKind,
+    pub grid_glyphs: GridGlyphs,
+    pub seed: Option<u64>,
+    /// Starting conditions this run was created under.
+    pub preset: Preset,
+    /// Set for `--daily` runs, which never touch the save file.
+    pub daily: Option<DailyChallenge>,
     pub should_quit: bool,
     pub paused: bool,
+    /// Preference: pause automatically when the terminal loses focus.
+    pub pause_on_focus_loss: bool,
+    /// Set when the current pause came from losing focus, so regaining it resumes.
+    paused_by_focus: bool,
     pub pause_menu: PauseMenuState,
     pub mining: MiningState,
     pub hashpower: HashpowerState,
     pub bank: BankState,
     pub ledger: LedgerState,
     pub ticker: TickerState,
-    pub messages: VecDeque<String>,
+    pub shard_ticker: TickerState,
+    /// Which asset the Bank pane's trade keys act on.
+    pub bank_asset: Asset,
+    pub messages: VecDeque<FeedMessage>,
+    pub stats: SessionStats,
+    pub legacy_multiplier: f64,
+    pub show_stats: bool,
+    /// Detail popup for the highlighted contract in the Mining pane.
+    pub show_contract_details: bool,
+    pub show_debug: bool,
+    pub show_achievements: bool,
+    pub achievements: HashSet<AchievementId>,
+    pub debug: DebugTimings,
+    pub order_draft: Option<LimitOrder>,
+    pub alert_draft: Option<PriceAlert>,
+    /// "Mine until" target announced (and optionally acted on) once reached.
+    pub goal: Option<Goal>,
+    pub goal_draft: Option<Goal>,
+    pub pending_purchase: Option<PendingPurchase>,
+    pub pending_manipulation: Option<PendingManipulation>,
+    last_action: Option<LastAction>,
+    undo_expires_in: f64,
+    /// Opened on first copy and kept alive, since some platforms drop the
+    /// contents when the owning handle goes away.
+    clipboard: Option<Clipboard>,
+    /// Opt-in on-disk copy of the message feed.
+    activity_log: Option<ActivityLog>,
+    pub session_started: Instant,
+    /// Stamps finished jobs; swapped for a simulated clock in headless runs.
+    pub clock: Box<dyn Clock>,
+    pub pane_rects: Cell<PaneRects>,
+    pub autosave_interval: Duration,
+    autosave_elapsed: Duration,
+    /// Stays false while an untouched save from an earlier session is on disk,
+    /// so autosave never clobbers it before the player saves or loads.
+    autosave_armed: bool,
+    /// Set by notable events when `config.bell` is on; main rings and clears it.
+    bell_pending: bool,
+    /// Set by the snapshot key; main renders the capture off-screen.
+    snapshot_requested: bool,
     rng: StdRng,
 }
 
+/// Screen regions recorded by the last draw, used to hit-test mouse events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PaneRects {
+    pub mining: Rect,
+    pub hashpower: Rect,
+    pub bank: Rect,
+    pub ledger: Rect,
+    pub contracts: ListRegion,
+    pub tiers: ListRegion,
+    pub ledger_rows: ListRegion,
+    /// Ledger rows that fit on screen, which is how far PageUp/PageDown move.
+    pub ledger_page: usize,
+}
+
+impl PaneRects {
+    fn pane_at(&self, column: u16, row: u16) -> Option<PaneFocus> {
+        [
+            (self.mining, PaneFocus::Mining),
+            (self.hashpower, PaneFocus::Hashpower),
+            (self.bank, PaneFocus::Bank),
+            (self.ledger, PaneFocus::Ledger),
+        ]
+        .into_iter()
+        .find(|(rect, _)| rect_contains(*rect, column, row))
+        .map(|(_, pane)| pane)
+    }
+}
+
+/// A rendered list area and the index of its first visible row.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ListRegion {
+    pub area: Rect,
+    pub offset: usize,
+}
+
+impl ListRegion {
+    fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if rect_contains(self.area, column, row) {
+            Some(self.offset + (row - self.area.y) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+fn rect_contains(rect: Rect, column: u16, row: u16) -> bool {
+    column >= rect.x && column < rect.right() && row >= rect.y && row < rect.bottom()
+}
+
+/// Frame and tick timings measured by the main loop for the debug overlay.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DebugTimings {
+    pub draw_time: Duration,
+    pub tick_interval: Duration,
+    last_tick_at: Option<Instant>,
+}
+
+/// An expensive hashpower purchase awaiting a yes/no from the player.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingPurchase {
+    pub tier: usize,
+    pub name: &'static str,
+    pub count: u32,
+    pub cost: f64,
+}
+
+/// A pump or dump awaiting a yes/no from the player. The whole position
+/// trades at the midpoint of the price swing it causes.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingManipulation {
+    pub side: OrderSide,
+    pub asset: Asset,
+    pub amount: f64,
+    pub shock: f64,
+    pub fill_price: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageCategory {
+    #[default]
+    Info,
+    Trade,
+    Mining,
+    Error,
+}
+
+/// One line of the message feed; `count` tracks identical messages in a row.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedMessage {
+    pub text: String,
+    #[serde(default)]
+    pub category: MessageCategory,
+    #[serde(default = "default_message_count")]
+    pub count: u32,
+}
+
+fn default_message_count() -> u32 {
+    1
+}
+
+impl FeedMessage {
+    pub fn display(&self) -> String {
+        if self.count > 1 {
+            format!("{} (×{})", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// The most recent manual trade or purchase, kept for a single-level undo.
+#[derive(Debug, Clone, Copy)]
+enum LastAction {
+    Trade {
+        asset: Asset,
+        side: OrderSide,
+        amount: f64,
+        credits: f64,
+        /// Part of a sale's proceeds that went straight to paying down debt.
+        repaid: f64,
+        /// How far the trade walked the price; undone along with the trade.
+        price_move: f64,
+    },
+    Purchase {
+        tier: usize,
+        count: u32,
+        cost: f64,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub links_restored: u64,
+    pub chain_mined: f64,
+    #[serde(default)]
+    pub shards_mined: f64,
+    pub credits_from_sales: f64,
+    /// Highest sale price seen, as a multiple of the long moving average.
+    #[serde(default)]
+    pub best_sale_ratio: f64,
+    /// Unpaused time across every session on this save.
+    #[serde(default)]
+    pub play_time_secs: f64,
+}
+
 impl App {
-    pub fn new() -> Result<Self> {
-        let mut rng = StdRng::from_entropy();
-        let mut mining = MiningState::new();
-        for _ in 0..JOB_POOL_SIZE {
-            mining.available_jobs.push(generate_job(&mut rng));
+    pub fn new(seed: Option<u64>, preset: Preset) -> Result<Self> {
+        Self::launch(|config, keymap| Self::fresh(seed, preset, config, keymap))
+    }
+
+    /// The `--daily` run for `date`, dealt from shared rules so only the
+    /// player's cosmetic settings and keymap apply.
+    pub fn new_daily(date: NaiveDate) -> Result<Self> {
+        Self::launch(|config, keymap| Self::fresh_daily(date, config, keymap))
+    }
+
+    fn launch(build: impl FnOnce(GameConfig, Keymap) -> Self) -> Result<Self> {
+        let (config, config_warning) = GameConfig::load_or_default(CONFIG_FILE);
+        let (keymap, keymap_warning) = Keymap::load_or_default(KEYMAP_FILE);
+        let mut app = build(config, keymap);
+        for warning in [config_warning, keymap_warning].into_iter().flatten() {
+            app.push_message(MessageCategory::Error, warning);
+        }
+        if app.config.log_activity {
+            app.enable_activity_log();
         }
+        Ok(app)
+    }
 
-        let mut hashpower = HashpowerState::default();
-        hashpower.tiers[0].owned = 1; // Give the player a humble starting rig.
+    /// A brand-new run with the given settings; shared by startup and New Game.
+    /// A fixed seed replays the same run every time it is dealt.
+    fn fresh(seed: Option<u64>, preset: Preset, config: GameConfig, keymap: Keymap) -> Self {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let hashpower = HashpowerState::starting(preset);
+        let mut mining = MiningState::new(&config, preset);
+        mining.replenish_pool(&mut rng, hashpower.total_power());
+        let bank = BankState::starting(preset, preset.spread(config.spread));
+        let history_cap = config.price_history;
 
-        Ok(Self {
+        Self {
             focus: PaneFocus::Mining,
+            zoom: None,
+            config,
+            keymap,
+            theme: ThemeKind::default(),
+            grid_glyphs: GridGlyphs::default(),
+            seed,
+            preset,
+            daily: None,
             should_quit: false,
             paused: false,
+            pause_on_focus_loss: true,
+            paused_by_focus: false,
             pause_menu: PauseMenuState::default(),
             mining,
             hashpower,
-            bank: BankState::default(),
+            bank,
             ledger: LedgerState::default(),
-            ticker: TickerState::new(32.0, &mut rng),
+            ticker: TickerState::new(32.0, history_cap, &mut rng),
+            shard_ticker: TickerState::new(SHARD_INITIAL_PRICE, history_cap, &mut rng),
+            bank_asset: Asset::default(),
             messages: VecDeque::new(),
+            stats: SessionStats::default(),
+            legacy_multiplier: 1.0,
+            show_stats: false,
+            show_contract_details: false,
+            show_debug: false,
+            show_achievements: false,
+            achievements: HashSet::new(),
+            debug: DebugTimings::default(),
+            order_draft: None,
+            alert_draft: None,
+            goal: None,
+            goal_draft: None,
+            pending_purchase: None,
+            pending_manipulation: None,
+            last_action: None,
+            undo_expires_in: 0.0,
+            clipboard: None,
+            activity_log: None,
+            session_started: Instant::now(),
+            clock: Box::new(SystemClock),
+            pane_rects: Cell::new(PaneRects::default()),
+            autosave_interval: Duration::from_secs(DEFAULT_AUTOSAVE_SECS),
+            autosave_elapsed: Duration::ZERO,
+            autosave_armed: !Path::new(SAVE_FILE).exists(),
+            bell_pending: false,
+            snapshot_requested: false,
             rng,
-        })
+        }
+    }
+
+    /// Replaces the run with a fresh one in place, keeping display and save
+    /// preferences. The pause menu closes as the new game starts.
+    fn restart(&mut self) {
+        let fresh = Self::fresh(
+            self.seed,
+            self.preset,
+            self.config.clone(),
+            self.keymap.clone(),
+        );
+        let previous = std::mem::replace(self, fresh);
+        self.theme = previous.theme;
+        self.grid_glyphs = previous.grid_glyphs;
+        self.pause_on_focus_loss = previous.pause_on_focus_loss;
+        self.autosave_interval = previous.autosave_interval;
+        self.clipboard = previous.clipboard;
+        self.activity_log = previous.activity_log;
+        self.clock = previous.clock;
+        self.push_message(
+            MessageCategory::Info,
+            "New game started. Fresh contracts surfaced.",
+        );
+    }
+
+    fn fresh_daily(date: NaiveDate, config: GameConfig, keymap: Keymap) -> Self {
+        let mut app = Self::fresh(
+            Some(daily::seed_for(date)),
+            Preset::Normal,
+            config.daily_rules(),
+            keymap,
+        );
+        app.start_daily(date);
+        app
+    }
+
+    fn start_daily(&mut self, date: NaiveDate) {
+        self.daily = Some(DailyChallenge::new(date));
+        self.autosave_armed = false;
+        self.push_message(
+            MessageCategory::Info,
+            format!(
+                "Daily challenge {}: reach {:.0}₵ net worth in {:.0} minutes. Saving is off.",
+                date.format("%Y-%m-%d"),
+                DAILY_GOAL_NET_WORTH,
+                DAILY_TIME_LIMIT_SECS / 60.0
+            ),
+        );
+    }
+
+    fn advance_daily(&mut self, secs: f64) {
+        let net_worth = self.net_worth();
+        let Some(daily) = self.daily.as_mut() else {
+            return;
+        };
+        let Some(outcome) = daily.advance(secs, net_worth) else {
+            return;
+        };
+        let score = daily.score;
+        let message = match outcome {
+            DailyOutcome::GoalReached { secs } => format!(
+                "Daily goal reached in {}! Score {}",
+                format_duration(Duration::from_secs_f64(secs)),
+                score
+            ),
+            DailyOutcome::TimeUp => format!("Daily challenge over. Score {}", score),
+        };
+        self.push_message(MessageCategory::Info, message);
+    }
+
+    fn daily_finished(&self) -> bool {
+        self.daily.as_ref().is_some_and(DailyChallenge::is_finished)
+    }
+
+    pub fn record_draw_time(&mut self, elapsed: Duration) {
+        self.debug.draw_time = elapsed;
     }
 
     pub fn on_tick(&mut self, dt: Duration) {
-        if self.paused {
+        let now = Instant::now();
+        if let Some(last) = self.debug.last_tick_at {
+            self.debug.tick_interval = now - last;
+        }
+        self.debug.last_tick_at = Some(now);
+        if let Some(log) = self.activity_log.as_mut()
+            && let Err(err) = log.maybe_flush()
+        {
+            self.activity_log = None;
+            self.push_message(
+                MessageCategory::Error,
+                format!("Activity log stopped: {:#}", err),
+            );
+        }
+        if self.paused || self.daily_finished() {
             return;
         }
+        self.clock.advance(dt);
         let secs = dt.as_secs_f64();
+        self.stats.play_time_secs += secs;
+        self.undo_expires_in -= secs;
+        if self.undo_expires_in <= 0.0 {
+            self.last_action = None;
+        }
         self.ticker.tick(dt, &mut self.rng);
+        self.shard_ticker.tick(dt, &mut self.rng);
+        if let Some(kind) = self.ticker.roll_event(dt, &mut self.rng) {
+            self.push_message(
+                MessageCategory::Info,
+                format!("MARKET: {}", kind.announcement()),
+            );
+        }
 
-        let power = self.hashpower.total_power();
-        if let Some(completed) = self.mining.apply_work(power * secs) {
-            let price = self.ticker.price;
-            let credits_value = completed.job.payout_chain * price;
-            let id = generate_link_id(&completed.job);
+        self.bank
+            .accrue_interest(secs, self.config.savings_interest);
+        self.bank.accrue_staking(secs);
+        self.bank.manipulation_cooldown = (self.bank.manipulation_cooldown - secs).max(0.0);
+        let power = self.hashpower.effective_power();
+        let credits_before = self.bank.credits_balance;
+        if let Some(ended) = self.hashpower.advance_overclock(secs, &mut self.bank) {
+            self.push_message(MessageCategory::Info, ended);
+        }
+        if credits_before > 0.0 && self.bank.credits_balance <= 0.0 {
+            self.ring_bell();
+        }
+        self.mining.advance_flash();
+        if let Some(completed) = self.mining.apply_work(power * secs, dt, self.clock.now()) {
+            let asset = completed.job.asset;
+            let price = self.ticker_for(asset).price;
+            let payout = completed.job.payout_chain * self.legacy_multiplier;
+            let credits_value = payout * price;
+            let id = generate_link_id(&completed.job, &mut self.rng);
             let message = format!(
-                "{} restored for {:.2} ⛓ ({:.2}₵)",
-                id, completed.job.payout_chain, credits_value
+                "{} restored for {:.2} {} ({:.2}₵)",
+                id,
+                payout,
+                asset.symbol(),
+                credits_value
             );
-            self.push_message(message);
-            self.bank.chain_balance += completed.job.payout_chain;
+            self.push_message(MessageCategory::Mining, message);
+            *self.bank.balance_mut(asset) += payout;
+            self.stats.links_restored += 1;
+            match asset {
+                Asset::Chain => self.stats.chain_mined += payout,
+                Asset::Shards => self.stats.shards_mined += payout,
+            }
             let delta = self
-                .ticker
+                .ticker_for_mut(asset)
                 .apply_market_nudge(completed.job.market_impact, completed.job.payout_chain);
             let entry = LedgerEntry {
                 id,
+                asset,
                 name: completed.job.name,
                 finished_at: completed.finished_at,
                 difficulty: completed.job.difficulty,
-                payout_chain: completed.job.payout_chain,
+                payout_chain: payout,
                 credits_at_completion: credits_value,
                 duration: completed.duration,
                 market_impact: delta,
             };
             self.ledger.add_entry(entry);
-            self.mining.available_jobs.push(generate_job(&mut self.rng));
         }
+        self.apply_auto_sell();
+
+        for alert in self.ticker.take_fired_alerts() {
+            self.push_message(
+                MessageCategory::Info,
+                format!("ALERT: Chain crossed {:.2}₵", alert.price),
+            );
+            self.ring_bell();
+        }
+        self.process_limit_orders();
+        let mythic_before = self.mining.count_rarity(JobRarity::Mythic);
+        let expired =
+            self.mining
+                .advance_refresh(secs, &mut self.rng, self.hashpower.total_power());
+        for name in expired {
+            self.push_message(
+                MessageCategory::Mining,
+                format!("Contract expired: {}", name),
+            );
+        }
+        if self.mining.count_rarity(JobRarity::Mythic) > mythic_before {
+            self.push_message(MessageCategory::Mining, "A Mythic contract surfaced");
+            self.ring_bell();
+        }
+
+        self.mining.quotes = self.quotes();
+        if self.mining.auto_accept && self.mining.active_job.is_none() {
+            self.mining.select_best_value();
+            self.accept_selected_job();
+        }
+
+        self.check_achievements();
+        self.check_goal();
+        self.advance_daily(secs);
+        self.advance_autosave(dt);
+    }
+
+    /// A zero interval disables autosave.
+    fn advance_autosave(&mut self, dt: Duration) {
+        if self.autosave_interval.is_zero() || !self.autosave_armed {
+            return;
+        }
+        self.autosave_elapsed += dt;
+        if self.autosave_elapsed < self.autosave_interval {
+            return;
+        }
+        self.autosave_elapsed = Duration::ZERO;
+        match self.save_game() {
+            Ok(()) => self.push_message(MessageCategory::Info, "Autosaved."),
+            Err(err) => {
+                self.push_message(MessageCategory::Error, format!("Autosave failed: {}", err))
+            }
+        }
+    }
+
+    fn cycle_autosave_interval(&mut self) {
+        let current = self.autosave_interval.as_secs();
+        let next = AUTOSAVE_PRESETS_SECS
+            .iter()
+            .copied()
+            .find(|&secs| secs > current)
+            .unwrap_or(AUTOSAVE_PRESETS_SECS[0]);
+        self.autosave_interval = Duration::from_secs(next);
+        self.autosave_elapsed = Duration::ZERO;
+    }
+
+    fn process_limit_orders(&mut self) {
+        let depth = self.config.market_depth;
+        for event in self.bank.execute_limit_orders(&mut self.ticker, depth) {
+            match event {
+                LimitOrderEvent::Filled { order, fill } => {
+                    self.last_action = None;
+                    let value = fill.credits;
+                    if order.side == OrderSide::Sell {
+                        self.stats.credits_from_sales += value;
+                        self.note_sale_price(Asset::Chain);
+                        self.repay_from_sale(value);
+                    }
+                    self.push_message(
+                        MessageCategory::Trade,
+                        format!(
+                            "Limit {} filled: {:.1} ⛓ for {:.2}₵ (avg {}₵, trigger {:.2}₵)",
+                            order.side.label(),
+                            order.amount,
+                            value,
+                            format_price(fill.average_price),
+                            order.trigger_price
+                        ),
+                    );
+                }
+                LimitOrderEvent::Blocked { order } => {
+                    self.push_message(
+                        MessageCategory::Error,
+                        format!(
+                            "Limit {} of {:.1} ⛓ triggered but can't be covered; still pending",
+                            order.side.label(),
+                            order.amount
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn achievement_progress(&self) -> Progress {
+        Progress {
+            links_restored: self.stats.links_restored,
+            net_worth: self.net_worth(),
+            foundry_cores_owned: self.hashpower.tiers[FOUNDRY_CORE_TIER].owned,
+            best_sale_ratio: self.stats.best_sale_ratio,
+        }
+    }
+
+    fn check_achievements(&mut self) {
+        let progress = self.achievement_progress();
+        for id in AchievementId::ALL {
+            if !self.achievements.contains(&id) && id.is_met(&progress) {
+                self.achievements.insert(id);
+                self.push_message(
+                    MessageCategory::Info,
+                    format!("★ Achievement unlocked: {}", id.title()),
+                );
+            }
+        }
+    }
 
-        self.mining.replenish_pool(&mut self.rng);
+    /// Tracks how far above trend a sale landed, for the spike achievement.
+    fn note_sale_price(&mut self, asset: Asset) {
+        let ticker = self.ticker_for(asset);
+        if let Some(average) = ticker.moving_average(LONG_MA_WINDOW)
+            && average > f64::EPSILON
+        {
+            let ratio = ticker.price / average;
+            self.stats.best_sale_ratio = self.stats.best_sale_ratio.max(ratio);
+        }
     }
 
-    fn push_message(&mut self, msg: impl Into<String>) {
-        self.messages.push_front(msg.into());
+    /// Adds to the feed, folding a repeat of the newest entry into its count.
+    fn push_message(&mut self, category: MessageCategory, msg: impl Into<String>) {
+        let msg = msg.into();
+        if let Some(log) = self.activity_log.as_mut()
+            && let Err(err) = log.record(&msg)
+        {
+            self.activity_log = None;
+            self.push_message(
+                MessageCategory::Error,
+                format!("Activity log stopped: {:#}", err),
+            );
+        }
+        if let Some(newest) = self.messages.front_mut()
+            && newest.category == category
+            && newest.text == msg
+        {
+            newest.count += 1;
+            return;
+        }
+        self.messages.push_front(FeedMessage {
+            text: msg,
+            category,
+            count: 1,
+        });
         while self.messages.len() > MAX_MESSAGES {
             self.messages.pop_back();
         }
     }
 
+    /// Starts appending every message to `LOG_FILE`.
+    pub fn enable_activity_log(&mut self) {
+        if self.activity_log.is_some() {
+            return;
+        }
+        match ActivityLog::open(LOG_FILE) {
+            Ok(log) => {
+                let path = log.path().display().to_string();
+                self.activity_log = Some(log);
+                self.push_message(
+                    MessageCategory::Info,
+                    format!("Logging activity to {}", path),
+                );
+            }
+            Err(err) => self.push_message(
+                MessageCategory::Error,
+                format!("Activity log unavailable: {:#}", err),
+            ),
+        }
+    }
+
     pub fn on_key(&mut self, key: KeyEvent) {
-        if !matches!(key.kind, KeyEventKind::Press) {
+        match key.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat if self.accepts_repeat(&key) => {}
+            _ => return,
+        }
+
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if self.pause_menu.confirm != Some(PauseConfirm::SaveBeforeExit) {
+                self.request_quit();
+            }
+            return;
+        }
+
+        if self.daily_finished() {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+                self.should_quit = true;
+            }
             return;
         }
 
@@ -214,18 +918,83 @@ impl App {
             return;
         }
 
-        if matches!(key.code, KeyCode::Char('q' | 'Q')) {
-            self.enter_pause();
+        if let Some(pending) = self.pending_purchase {
+            self.handle_purchase_confirm(pending, key);
             return;
         }
 
-        match key.code {
-            KeyCode::Tab => {
+        if let Some(pending) = self.pending_manipulation {
+            self.handle_manipulation_confirm(pending, key);
+            return;
+        }
+
+        if self.show_stats && key.code == KeyCode::Esc {
+            self.show_stats = false;
+            return;
+        }
+
+        if self.show_contract_details && key.code == KeyCode::Esc {
+            self.show_contract_details = false;
+            return;
+        }
+
+        // Open editors swallow every key so global bindings can't leave them
+        // pending out of view.
+        if self.goal_draft.is_some() {
+            self.handle_goal_entry(key);
+            return;
+        }
+
+        if self.order_draft.is_some() {
+            self.handle_order_entry(key);
+            return;
+        }
+
+        if self.alert_draft.is_some() {
+            self.handle_alert_entry(key);
+            return;
+        }
+
+        // Typing a ledger query must not trigger single-letter global bindings.
+        if self.focus == PaneFocus::Ledger && self.ledger.filter_editing {
+            self.handle_filter_entry(key);
+            return;
+        }
+
+        match self.keymap.action(&[Scope::Global], &key) {
+            Some(Action::Pause) => self.enter_pause(),
+            Some(Action::ToggleStats) => self.show_stats = !self.show_stats,
+            Some(Action::ToggleDebug) => self.show_debug = !self.show_debug,
+            Some(Action::Undo) => self.undo_last_action(),
+            Some(Action::ToggleChart) => self.ticker.chart = self.ticker.chart.toggled(),
+            Some(Action::ToggleChartScale) => {
+                self.ticker.chart_scale = self.ticker.chart_scale.toggled();
+            }
+            Some(Action::Snapshot) => self.snapshot_requested = true,
+            Some(Action::SetGoal) => {
+                self.goal_draft = Some(match &self.goal {
+                    Some(goal) => goal.clone(),
+                    None => Goal::above(GoalMetric::Chain, self.goal_value(GoalMetric::Chain)),
+                });
+            }
+            Some(Action::ToggleZoom) => {
+                self.zoom = match self.zoom {
+                    Some(_) => None,
+                    None => Some(self.focus),
+                };
+            }
+            Some(Action::NextPane) => {
                 self.focus = self.focus.next();
+                self.follow_zoom();
             }
-            KeyCode::BackTab => {
+            Some(Action::PrevPane) => {
                 self.focus = self.focus.prev();
+                self.follow_zoom();
             }
+            Some(Action::FocusMining) => self.jump_to_pane(PaneFocus::Mining),
+            Some(Action::FocusHashpower) => self.jump_to_pane(PaneFocus::Hashpower),
+            Some(Action::FocusBank) => self.jump_to_pane(PaneFocus::Bank),
+            Some(Action::FocusLedger) => self.jump_to_pane(PaneFocus::Ledger),
             _ => match self.focus {
                 PaneFocus::Mining => self.handle_mining_input(key),
                 PaneFocus::Hashpower => self.handle_hashpower_input(key),
@@ -235,1040 +1004,1676 @@ impl App {
         }
     }
 
+    pub fn on_mouse(&mut self, event: MouseEvent) {
+        if self.paused || self.pending_purchase.is_some() || self.pending_manipulation.is_some() {
+            return;
+        }
+        let rects = self.pane_rects.get();
+        let (column, row) = (event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(pane) = rects.pane_at(column, row) else {
+                    return;
+                };
+                self.focus = pane;
+                self.follow_zoom();
+                match pane {
+                    PaneFocus::Mining => {
+                        if let Some(pos) = rects.contracts.row_at(column, row) {
+                            let order = self.mining.display_order();
+                            if let Some(&idx) = order.get(pos) {
+                                self.mining.selected_job = idx;
+                            }
+                        }
+                    }
+                    PaneFocus::Hashpower => {
+                        if let Some(idx) = rects.tiers.row_at(column, row) {
+                            match self.hashpower.view {
+                                HashpowerView::Tiers if idx < self.hashpower.tiers.len() => {
+                                    self.hashpower.selected = idx;
+                                }
+                                HashpowerView::Research if idx < self.hashpower.research.len() => {
+                                    self.hashpower.research_selected = idx;
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    PaneFocus::Ledger => {
+                        if let Some(idx) = rects.ledger_rows.row_at(column, row)
+                            && idx < self.ledger.visible_entries().len()
+                        {
+                            self.ledger.scroll = idx;
+                        }
+                    }
+                    PaneFocus::Bank => {}
+                }
+            }
+            MouseEventKind::ScrollUp if rect_contains(rects.ledger, column, row) => {
+                self.ledger.scroll_up();
+            }
+            MouseEventKind::ScrollDown if rect_contains(rects.ledger, column, row) => {
+                self.ledger.scroll_down();
+            }
+            _ => {}
+        }
+    }
+
+    fn follow_zoom(&mut self) {
+        if self.zoom.is_some() {
+            self.zoom = Some(self.focus);
+        }
+    }
+
+    /// Handles terminal focus reports, which only arrive when the terminal supports them.
+    pub fn on_focus_change(&mut self, gained: bool) {
+        if gained {
+            // Leave the menu up if the player started using it while away.
+            if self.paused_by_focus && self.paused && self.pause_menu.confirm.is_none() {
+                self.resume();
+            }
+            self.paused_by_focus = false;
+        } else if self.pause_on_focus_loss && !self.paused {
+            self.enter_pause();
+            self.pause_menu
+                .set_status(Some("Paused while the terminal is unfocused.".to_string()));
+            self.paused_by_focus = true;
+        }
+    }
+
     fn enter_pause(&mut self) {
         self.paused = true;
+        self.paused_by_focus = false;
+        self.show_achievements = false;
         self.pause_menu.set_status(None);
+        self.pause_menu.confirm = None;
     }
 
     fn resume(&mut self) {
         self.paused = false;
         self.pause_menu.set_status(None);
+        self.pause_menu.confirm = None;
+    }
+
+    pub fn net_worth(&self) -> f64 {
+        self.bank.credits_balance
+            + (self.bank.chain_balance + self.bank.staked) * self.ticker.price
+            + self.bank.shard_balance * self.shard_ticker.price
+            - self.bank.debt
+    }
+
+    /// Most the bank will lend in total, growing with installed hashpower.
+    pub fn loan_limit(&self) -> f64 {
+        LOAN_BASE_LIMIT + self.hashpower.total_power() * LOAN_LIMIT_PER_POWER
+    }
+
+    pub fn ticker_for(&self, asset: Asset) -> &TickerState {
+        match asset {
+            Asset::Chain => &self.ticker,
+            Asset::Shards => &self.shard_ticker,
+        }
+    }
+
+    fn ticker_for_mut(&mut self, asset: Asset) -> &mut TickerState {
+        match asset {
+            Asset::Chain => &mut self.ticker,
+            Asset::Shards => &mut self.shard_ticker,
+        }
+    }
+
+    pub fn quotes(&self) -> AssetQuotes {
+        AssetQuotes {
+            chain: self.ticker.price,
+            shards: self.shard_ticker.price,
+        }
+    }
+
+    pub fn can_prestige(&self) -> bool {
+        self.net_worth() >= PRESTIGE_THRESHOLD && self.bank.debt <= 0.0
+    }
+
+    pub fn projected_legacy_multiplier(&self) -> f64 {
+        self.legacy_multiplier + prestige_gain(self.net_worth())
+    }
+
+    fn prestige(&mut self) {
+        let cashed_in = self.net_worth();
+        self.legacy_multiplier += prestige_gain(cashed_in);
+        self.bank = BankState::starting(self.preset, self.bank.spread);
+        self.hashpower = HashpowerState::starting(self.preset);
+        self.mining = MiningState::new(&self.config, self.preset);
+        self.mining
+            .replenish_pool(&mut self.rng, self.hashpower.total_power());
+        self.ledger = LedgerState::default();
+        self.last_action = None;
+        self.push_message(
+            MessageCategory::Info,
+            format!(
+                "Prestiged {:.2}₵ of net worth. Legacy payouts now ×{:.2}",
+                cashed_in, self.legacy_multiplier
+            ),
+        );
+    }
+
+    pub fn accept_selected_job(&mut self) -> bool {
+        if let Some(job) = self.mining.take_selected_job() {
+            self.last_action = None;
+            let name = job.name.clone();
+            self.mining.active_job = Some(ActiveJob::new(job));
+            self.push_message(
+                MessageCategory::Mining,
+                format!("Accepted mining contract: {}", name),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Held keys only auto-repeat for list movement, so holding Enter or a
+    /// purchase key can never fire it twice.
+    fn accepts_repeat(&self, key: &KeyEvent) -> bool {
+        let list_focused = matches!(
+            self.focus,
+            PaneFocus::Mining | PaneFocus::Hashpower | PaneFocus::Ledger
+        );
+        let modal_open = self.paused
+            || self.pending_purchase.is_some()
+            || self.pending_manipulation.is_some()
+            || self.ledger.filter_editing;
+        list_focused && !modal_open && self.keymap.action(&[Scope::Navigation], key).is_some()
+    }
+
+    fn jump_to_pane(&mut self, pane: PaneFocus) {
+        self.focus = pane;
+        self.follow_zoom();
     }
 
     fn handle_mining_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up => self.mining.select_previous(),
-            KeyCode::Down => self.mining.select_next(),
-            KeyCode::Enter => {
-                if let Some(job) = self.mining.take_selected_job() {
-                    let name = job.name.clone();
-                    self.mining.active_job = Some(ActiveJob::new(job));
-                    self.push_message(format!("Accepted mining contract: {}", name));
-                }
+        match self
+            .keymap
+            .action(&[Scope::Navigation, Scope::Mining], &key)
+        {
+            Some(Action::MoveUp) => self.mining.select_previous(),
+            Some(Action::MoveDown) => self.mining.select_next(),
+            Some(Action::AcceptContract) => {
+                self.accept_selected_job();
             }
-            KeyCode::Char('r') => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.mining.shuffle_jobs(&mut self.rng);
-                    self.push_message("Contracts reshuffled".to_string());
+            Some(Action::RerollContracts) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let power = self.hashpower.total_power();
+                match self.mining.reroll(&mut self.rng, power) {
+                    Ok(()) => {
+                        self.push_message(MessageCategory::Mining, "Contracts rerolled".to_string())
+                    }
+                    Err(err) => {
+                        self.push_message(MessageCategory::Error, format!("Can't reroll: {}", err))
+                    }
                 }
             }
+            Some(Action::ToggleAutoAccept) => {
+                self.mining.auto_accept = !self.mining.auto_accept;
+                let state = if self.mining.auto_accept { "on" } else { "off" };
+                self.push_message(MessageCategory::Mining, format!("Auto-accept {}", state));
+            }
+            Some(Action::CycleSort) => self.mining.cycle_sort(),
+            Some(Action::ContractDetails) => {
+                self.show_contract_details = !self.show_contract_details;
+            }
+            Some(Action::ToggleStrategy) => {
+                self.mining.strategy = self.mining.strategy.toggled();
+                self.push_message(
+                    MessageCategory::Mining,
+                    format!("Work strategy: {}", self.mining.strategy.label()),
+                );
+            }
             _ => {}
         }
     }
 
     fn handle_hashpower_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up => self.hashpower.select_previous(),
-            KeyCode::Down => self.hashpower.select_next(),
-            KeyCode::Enter => {
-                if let Some(cost) = self.hashpower.purchase_selected(&mut self.bank) {
-                    self.push_message(format!(
-                        "Purchased {} for {:.2}₵",
-                        self.hashpower.selected_name(),
-                        cost
-                    ));
+        match self
+            .keymap
+            .action(&[Scope::Navigation, Scope::Hashpower], &key)
+        {
+            Some(Action::MoveUp) => self.hashpower.select_previous(),
+            Some(Action::MoveDown) => self.hashpower.select_next(),
+            Some(Action::Overclock) => match self.hashpower.engage_overclock() {
+                Ok(()) => {
+                    self.last_action = None;
+                    self.push_message(
+                        MessageCategory::Info,
+                        format!(
+                            "Overclocked ×{:.0} for {:.0}s, burning {:.2}₵/s",
+                            OVERCLOCK_MULTIPLIER,
+                            OVERCLOCK_DURATION_SECS,
+                            self.hashpower.overclock_drain_per_sec()
+                        ),
+                    );
+                }
+                Err(err) => {
+                    self.push_message(MessageCategory::Error, format!("Can't overclock: {}", err))
                 }
+            },
+            Some(Action::ToggleResearch) => self.hashpower.toggle_view(),
+            Some(Action::PurchaseTier) if self.hashpower.view == HashpowerView::Research => {
+                let index = self.hashpower.research_selected;
+                match self.hashpower.purchase_research(index, &mut self.bank) {
+                    Ok(()) => {
+                        self.last_action = None;
+                        let upgrade = &self.hashpower.research[index];
+                        let message = format!(
+                            "Researched {}: +{:.0}% {} output",
+                            upgrade.name,
+                            upgrade.power_bonus * 100.0,
+                            self.hashpower.tiers[upgrade.tier].name
+                        );
+                        self.push_message(MessageCategory::Trade, message);
+                    }
+                    Err(err) => self
+                        .push_message(MessageCategory::Error, format!("Can't research: {}", err)),
+                }
+            }
+            Some(Action::PurchaseTier | Action::PurchaseTen | Action::PurchaseMax)
+                if self.hashpower.view == HashpowerView::Tiers
+                    && !self.hashpower.is_unlocked(self.hashpower.selected) =>
+            {
+                let index = self.hashpower.selected;
+                let previous = self.hashpower.tiers[index - 1].name;
+                self.push_message(
+                    MessageCategory::Error,
+                    format!(
+                        "{} is locked: own {} {} first",
+                        self.hashpower.tiers[index].name, TIER_UNLOCK_OWNED, previous
+                    ),
+                );
+            }
+            Some(Action::PurchaseTen) if self.hashpower.view == HashpowerView::Tiers => {
+                self.request_purchase(Some(BULK_PURCHASE_COUNT));
             }
+            Some(Action::PurchaseMax) if self.hashpower.view == HashpowerView::Tiers => {
+                self.request_purchase(None);
+            }
+            Some(Action::PurchaseTier) => self.request_purchase(Some(1)),
             _ => {}
         }
     }
 
-    fn handle_bank_input(&mut self, key: KeyEvent) {
-        let price = self.ticker.price;
-        match key.code {
-            KeyCode::Left => {
-                if let Some(proceeds) = self.bank.sell_chain(1.0, price) {
-                    self.push_message(format!(
-                        "Sold 1.0 ⛓ for {:.2}₵ ({:.2}₵/⛓)",
-                        proceeds, proceeds
-                    ));
-                }
-            }
-            KeyCode::Right => {
-                if let Some(cost) = self.bank.buy_chain(1.0, price) {
-                    self.push_message(format!("Bought 1.0 ⛓ for {:.2}₵ ({:.2}₵/⛓)", cost, cost));
-                }
-            }
-            KeyCode::Char('m') => {
-                let amount = 5.0;
-                if let Some(proceeds) = self.bank.sell_chain(amount, price) {
-                    self.push_message(format!(
-                        "Market order: sold {:.1} ⛓ for {:.2}₵ ({:.2}₵/⛓)",
+    /// Market order for the asset being traded, reported with its average fill.
+    fn manual_trade(&mut self, side: OrderSide, amount: f64) {
+        let asset = self.bank_asset;
+        let symbol = asset.symbol();
+        match self.market_order(asset, side, amount) {
+            Ok(fill) => {
+                self.record_trade(asset, side, amount, fill.credits, fill.price_move);
+                let verb = match side {
+                    OrderSide::Sell => "Sold",
+                    OrderSide::Buy => "Bought",
+                };
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!(
+                        "{} {:.1} {} for {:.2}₵ (avg {}₵/{})",
+                        verb,
                         amount,
-                        proceeds,
-                        proceeds / amount
-                    ));
-                }
+                        symbol,
+                        fill.credits,
+                        format_price(fill.average_price),
+                        symbol
+                    ),
+                );
             }
-            KeyCode::Char('b') => {
-                let amount = 5.0;
-                if let Some(cost) = self.bank.buy_chain(amount, price) {
-                    self.push_message(format!(
-                        "Bulk order: bought {:.1} ⛓ for {:.2}₵ ({:.2}₵/⛓)",
-                        amount,
-                        cost,
-                        cost / amount
-                    ));
-                }
+            Err(err) => {
+                let verb = match side {
+                    OrderSide::Sell => "sell",
+                    OrderSide::Buy => "buy",
+                };
+                self.push_message(MessageCategory::Error, format!("Can't {}: {}", verb, err));
             }
-            _ => {}
         }
     }
 
-    fn handle_ledger_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up => self.ledger.scroll_up(),
-            KeyCode::Down => self.ledger.scroll_down(),
-            _ => {}
+    /// Fills `amount` at market, walking the asset's price by the trade's size.
+    pub fn market_order(
+        &mut self,
+        asset: Asset,
+        side: OrderSide,
+        amount: f64,
+    ) -> Result<MarketFill> {
+        let ticker = match asset {
+            Asset::Chain => &mut self.ticker,
+            Asset::Shards => &mut self.shard_ticker,
+        };
+        self.bank
+            .market_order(ticker, asset, side, amount, self.config.market_depth)
+    }
+
+    fn record_trade(
+        &mut self,
+        asset: Asset,
+        side: OrderSide,
+        amount: f64,
+        credits: f64,
+        price_move: f64,
+    ) {
+        let mut repaid = 0.0;
+        if side == OrderSide::Sell {
+            self.stats.credits_from_sales += credits;
+            self.note_sale_price(asset);
+            repaid = self.repay_from_sale(credits);
         }
+        self.remember_action(LastAction::Trade {
+            asset,
+            side,
+            amount,
+            credits,
+            repaid,
+            price_move,
+        });
+        self.check_achievements();
+    }
+
+    /// Keeps `action` undoable for `UNDO_WINDOW_SECS`.
+    fn remember_action(&mut self, action: LastAction) {
+        self.last_action = Some(action);
+        self.undo_expires_in = UNDO_WINDOW_SECS;
+    }
+
+    /// Withholds `LOAN_AUTO_REPAY_SHARE` of a sale towards any outstanding debt.
+    fn repay_from_sale(&mut self, proceeds: f64) -> f64 {
+        let repaid = self.bank.repay(proceeds * LOAN_AUTO_REPAY_SHARE);
+        if repaid > 0.0 {
+            self.push_message(
+                MessageCategory::Trade,
+                format!(
+                    "Put {:.2}₵ of the sale towards your loan ({:.2}₵ left)",
+                    repaid, self.bank.debt
+                ),
+            );
+        }
+        repaid
     }
 
-    fn handle_pause_input(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Up => self.pause_menu.select_previous(),
-            KeyCode::Down => self.pause_menu.select_next(),
-            KeyCode::Enter => self.activate_pause_selection(),
-            KeyCode::Esc => self.resume(),
-            KeyCode::Char('q' | 'Q') => self.resume(),
-            _ => {}
+    /// Sells any chain held above the auto-sell cap at market.
+    fn apply_auto_sell(&mut self) {
+        if !self.bank.auto_sell_enabled {
+            return;
+        }
+        let excess = self.bank.chain_balance - self.bank.auto_sell_cap;
+        if excess <= 0.0 {
+            return;
+        }
+        // Excess too small to cover the fee stays put until more is mined.
+        if let Ok(fill) = self.market_order(Asset::Chain, OrderSide::Sell, excess) {
+            self.last_action = None;
+            let proceeds = fill.credits;
+            self.stats.credits_from_sales += proceeds;
+            self.note_sale_price(Asset::Chain);
+            self.push_message(
+                MessageCategory::Trade,
+                format!(
+                    "Auto-sold {:.2} ⛓ above the {:.0} ⛓ cap for {:.2}₵",
+                    excess, self.bank.auto_sell_cap, proceeds
+                ),
+            );
+            self.repay_from_sale(proceeds);
         }
     }
 
-    fn activate_pause_selection(&mut self) {
-        match self.pause_menu.current() {
-            PauseMenuItem::Resume => self.resume(),
-            PauseMenuItem::Save => match self.save_game() {
-                Ok(()) => {
-                    self.pause_menu.set_status(Some("Game saved.".to_string()));
-                    self.push_message("Snapshot stored to disk.");
-                }
-                Err(err) => {
-                    self.pause_menu
-                        .set_status(Some(format!("Save failed: {}", err)));
-                    self.push_message(format!("Save error: {}", err));
-                }
-            },
-            PauseMenuItem::Load => match self.load_game() {
-                Ok(()) => {
-                    self.pause_menu.set_status(Some("Game loaded.".to_string()));
-                    self.push_message("Restored state from snapshot.");
-                }
-                Err(err) => {
-                    self.pause_menu
-                        .set_status(Some(format!("Load failed: {}", err)));
-                    self.push_message(format!("Load error: {}", err));
-                }
-            },
-            PauseMenuItem::Quit => {
-                self.should_quit = true;
+    fn take_loan(&mut self) {
+        let limit = self.loan_limit();
+        match self.bank.borrow(LOAN_STEP, limit) {
+            Ok(amount) => {
+                self.last_action = None;
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!(
+                        "Borrowed {:.2}₵ at {:.0}%/h (debt {:.2}₵ of {:.0}₵ limit)",
+                        amount,
+                        LOAN_RATE_PER_HOUR * 100.0,
+                        self.bank.debt,
+                        limit
+                    ),
+                );
             }
+            Err(err) => self.push_message(MessageCategory::Error, format!("Can't borrow: {}", err)),
         }
     }
 
-    fn save_game(&mut self) -> Result<()> {
-        let snapshot = SaveData::from_app(self);
-        let payload = serde_json::to_vec_pretty(&snapshot)?;
-        fs::write(SAVE_FILE, payload).with_context(|| format!("writing {}", SAVE_FILE))?;
-        Ok(())
-    }
-
-    fn load_game(&mut self) -> Result<()> {
-        if !Path::new(SAVE_FILE).exists() {
-            return Err(anyhow!("no save data available"));
+    fn repay_loan(&mut self) {
+        if self.bank.debt <= 0.0 {
+            self.push_message(MessageCategory::Error, "No outstanding loan");
+            return;
         }
-        let data = fs::read(SAVE_FILE).with_context(|| format!("reading {}", SAVE_FILE))?;
-        let snapshot: SaveData =
-            serde_json::from_slice(&data).context("parsing stored game state")?;
-        snapshot.apply(self)?;
-        Ok(())
-    }
-}
-
-#[derive(Debug, Default)]
-pub struct MiningState {
-    pub available_jobs: Vec<MiningJob>,
-    pub selected_job: usize,
-    pub active_job: Option<ActiveJob>,
-}
-
-impl MiningState {
-    fn new() -> Self {
-        Self {
-            available_jobs: Vec::new(),
-            selected_job: 0,
-            active_job: None,
+        let repaid = self.bank.repay(self.bank.credits_balance);
+        if repaid > 0.0 {
+            self.last_action = None;
+            self.push_message(
+                MessageCategory::Trade,
+                format!("Repaid {:.2}₵ ({:.2}₵ still owed)", repaid, self.bank.debt),
+            );
+        } else {
+            self.push_message(MessageCategory::Error, "Can't repay: no credits on hand");
         }
     }
 
-    pub fn apply_work(&mut self, work: f64) -> Option<CompletedJob> {
-        if let Some(active) = self.active_job.as_mut() {
-            active.apply_work(work);
-            if active.is_complete() {
-                let active = self.active_job.take().unwrap();
-                return Some(active.finish());
+    /// Reverses the last manual trade or purchase at its original price, as long
+    /// as the balances it would take back are still there.
+    fn undo_last_action(&mut self) {
+        let Some(action) = self.last_action else {
+            self.push_message(MessageCategory::Info, "Nothing to undo");
+            return;
+        };
+        match action {
+            LastAction::Trade {
+                asset,
+                side: OrderSide::Sell,
+                amount,
+                credits,
+                repaid,
+                price_move,
+            } => {
+                if self.bank.credits_balance + 1e-6 < credits - repaid {
+                    self.push_message(
+                        MessageCategory::Error,
+                        "Can't undo sale: credits already spent",
+                    );
+                    return;
+                }
+                self.bank.credits_balance -= credits - repaid;
+                self.bank.debt += repaid;
+                self.ticker_for_mut(asset).shift_price(-price_move);
+                *self.bank.balance_mut(asset) += amount;
+                self.stats.credits_from_sales -= credits;
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!("Undid sale of {:.1} {}", amount, asset.symbol()),
+                );
+            }
+            LastAction::Trade {
+                asset,
+                side: OrderSide::Buy,
+                amount,
+                credits,
+                price_move,
+                ..
+            } => {
+                if self.bank.balance(asset) + 1e-6 < amount {
+                    self.push_message(
+                        MessageCategory::Error,
+                        format!(
+                            "Can't undo buy: {} already spent",
+                            asset.label().to_lowercase()
+                        ),
+                    );
+                    return;
+                }
+                // The exchange keeps its flat fee on a reversed trade.
+                *self.bank.balance_mut(asset) -= amount;
+                self.bank.credits_balance += credits - EXCHANGE_FLAT_FEE;
+                self.ticker_for_mut(asset).shift_price(-price_move);
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!("Undid purchase of {:.1} {}", amount, asset.symbol()),
+                );
+            }
+            LastAction::Purchase { tier, count, cost } => {
+                let Some(tier) = self
+                    .hashpower
+                    .tiers
+                    .get_mut(tier)
+                    .filter(|tier| tier.owned >= count)
+                else {
+                    self.push_message(
+                        MessageCategory::Error,
+                        "Can't undo purchase: rigs no longer owned",
+                    );
+                    return;
+                };
+                tier.owned -= count;
+                self.bank.credits_balance += cost;
+                let name = tier.name;
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!("Refunded {}× {} for {:.2}₵", count, name, cost),
+                );
             }
         }
-        None
+        self.last_action = None;
     }
 
-    pub fn take_selected_job(&mut self) -> Option<MiningJob> {
-        if self.active_job.is_some() || self.available_jobs.is_empty() {
-            return None;
-        }
-        if self.selected_job >= self.available_jobs.len() {
-            self.selected_job = 0;
-        }
-        let job = self.available_jobs.remove(self.selected_job);
-        if self.available_jobs.is_empty() {
-            self.selected_job = 0;
-        } else if self.selected_job >= self.available_jobs.len() {
-            self.selected_job = self.available_jobs.len() - 1;
+    fn handle_purchase_confirm(&mut self, pending: PendingPurchase, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y' | 'Y') => {
+                self.pending_purchase = None;
+                self.buy_units(pending.tier, Some(pending.count));
+            }
+            KeyCode::Esc | KeyCode::Char('n' | 'N') => {
+                self.pending_purchase = None;
+                self.push_message(
+                    MessageCategory::Info,
+                    format!("Cancelled {} purchase", pending.name),
+                );
+            }
+            _ => {}
         }
-        Some(job)
     }
 
-    pub fn select_next(&mut self) {
-        if self.available_jobs.is_empty() {
+    /// Sizes a dump of every unit held, or a pump spending every credit, and
+    /// asks for confirmation before moving the market.
+    fn prepare_manipulation(&mut self, side: OrderSide) {
+        if self.bank.manipulation_cooldown > 0.0 {
+            self.push_message(
+                MessageCategory::Error,
+                format!(
+                    "Markets are watching; manipulation ready in {:.0}s",
+                    self.bank.manipulation_cooldown.ceil()
+                ),
+            );
             return;
         }
-        self.selected_job = (self.selected_job + 1) % self.available_jobs.len();
-    }
-
-    pub fn select_previous(&mut self) {
-        if self.available_jobs.is_empty() {
+        let asset = self.bank_asset;
+        let price = self.ticker_for(asset).price;
+        let value = match side {
+            OrderSide::Sell => self.bank.balance(asset) * price,
+            OrderSide::Buy => self.bank.credits_balance - EXCHANGE_FLAT_FEE,
+        };
+        if value < MANIPULATION_MIN_VALUE {
+            self.push_message(
+                MessageCategory::Error,
+                format!(
+                    "Need at least {:.0}₵ of {} to move the market",
+                    MANIPULATION_MIN_VALUE,
+                    match side {
+                        OrderSide::Sell => asset.label().to_lowercase(),
+                        OrderSide::Buy => "credits".to_string(),
+                    }
+                ),
+            );
             return;
         }
-        if self.selected_job == 0 {
-            self.selected_job = self.available_jobs.len() - 1;
-        } else {
-            self.selected_job -= 1;
-        }
+        let magnitude = MANIPULATION_MAX_SHOCK * value / (value + MANIPULATION_HALF_VALUE);
+        let shock = match side {
+            OrderSide::Sell => -magnitude,
+            OrderSide::Buy => magnitude,
+        };
+        let fill_price = price * (1.0 + shock / 2.0);
+        let amount = match side {
+            OrderSide::Sell => self.bank.balance(asset),
+            OrderSide::Buy => value / (fill_price * self.bank.buy_multiplier()),
+        };
+        self.pending_manipulation = Some(PendingManipulation {
+            side,
+            asset,
+            amount,
+            shock,
+            fill_price,
+        });
     }
 
-    fn replenish_pool(&mut self, rng: &mut StdRng) {
-        while self.available_jobs.len() < JOB_POOL_SIZE {
-            self.available_jobs.push(generate_job(rng));
+    fn handle_manipulation_confirm(&mut self, pending: PendingManipulation, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y' | 'Y') => {
+                self.pending_manipulation = None;
+                self.execute_manipulation(pending);
+            }
+            KeyCode::Esc | KeyCode::Char('n' | 'N') => {
+                self.pending_manipulation = None;
+                self.push_message(MessageCategory::Info, "Called off the market play");
+            }
+            _ => {}
         }
     }
 
-    fn shuffle_jobs(&mut self, rng: &mut StdRng) {
-        self.available_jobs.shuffle(rng);
-        self.selected_job = 0;
-    }
-
-    fn to_save(&self) -> MiningSave {
-        MiningSave {
-            available_jobs: self.available_jobs.clone(),
-            selected_job: self.selected_job,
-            active_job: self.active_job.as_ref().map(ActiveJobSave::from_active),
+    fn execute_manipulation(&mut self, pending: PendingManipulation) {
+        let PendingManipulation {
+            side,
+            asset,
+            amount,
+            shock,
+            fill_price,
+        } = pending;
+        let symbol = asset.symbol();
+        let mut sale_proceeds = None;
+        let message = match side {
+            OrderSide::Sell => self.bank.sell(asset, amount, fill_price).map(|proceeds| {
+                self.stats.credits_from_sales += proceeds;
+                sale_proceeds = Some(proceeds);
+                format!(
+                    "Dumped {:.1} {} for {:.2}₵; the market is reeling",
+                    amount, symbol, proceeds
+                )
+            }),
+            OrderSide::Buy => self.bank.buy(asset, amount, fill_price).map(|cost| {
+                format!(
+                    "Pumped {:.1} {} for {:.2}₵; buyers are piling in",
+                    amount, symbol, cost
+                )
+            }),
+        };
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                self.push_message(
+                    MessageCategory::Error,
+                    format!("Market play cancelled: {}", err),
+                );
+                return;
+            }
+        };
+        if side == OrderSide::Sell {
+            // Judged against the price before the shock lands, like any other sale.
+            self.note_sale_price(asset);
         }
-    }
-
-    fn apply_save(&mut self, save: MiningSave) {
-        self.available_jobs = save.available_jobs;
-        if self.available_jobs.is_empty() {
-            self.selected_job = 0;
-        } else {
-            self.selected_job = save.selected_job.min(self.available_jobs.len() - 1);
+        let kind = self.ticker_for_mut(asset).manipulate(shock);
+        self.bank.manipulation_cooldown = MANIPULATION_COOLDOWN_SECS;
+        // Undoing would hand back the position after the price has already moved.
+        self.last_action = None;
+        self.push_message(MessageCategory::Trade, message);
+        if let Some(proceeds) = sale_proceeds {
+            self.repay_from_sale(proceeds);
+        }
+        self.push_message(
+            MessageCategory::Info,
+            format!("MARKET: {}", kind.announcement()),
+        );
+        self.check_achievements();
+    }
+
+    /// Buys up to `limit` units of the selected tier, asking first when they
+    /// would spend more than `PURCHASE_CONFIRM_FRACTION` of the balance.
+    fn request_purchase(&mut self, limit: Option<u32>) {
+        let index = self.hashpower.selected;
+        let credits = self.bank.credits_balance;
+        match self.hashpower.quote_tier_bulk(index, limit, credits) {
+            Some((count, cost)) if cost > credits * PURCHASE_CONFIRM_FRACTION => {
+                self.pending_purchase = Some(PendingPurchase {
+                    tier: index,
+                    name: self.hashpower.tiers[index].name,
+                    count,
+                    cost,
+                });
+            }
+            _ => self.buy_units(index, limit),
         }
-        self.active_job = save.active_job.map(ActiveJob::from_save);
     }
-}
 
-#[derive(Debug)]
-pub struct ActiveJob {
-    pub job: MiningJob,
-    pub linklets: Vec<LinkletProgress>,
-    pub current_index: usize,
-    pub started_at: Instant,
-}
-
-impl ActiveJob {
-    fn new(job: MiningJob) -> Self {
-        let linklets = job
-            .linklet_difficulties
-            .iter()
-            .copied()
-            .map(LinkletProgress::new)
-            .collect();
-        Self {
-            job,
-            linklets,
-            current_index: 0,
-            started_at: Instant::now(),
+    fn buy_units(&mut self, index: usize, limit: Option<u32>) {
+        if limit == Some(1) {
+            self.complete_purchase(index);
+        } else {
+            self.purchase_bulk(index, limit);
         }
     }
 
-    fn apply_work(&mut self, mut work: f64) {
-        while work > 0.0 && self.current_index < self.linklets.len() {
-            let linklet = &mut self.linklets[self.current_index];
-            if linklet.remaining > work {
-                linklet.remaining -= work;
-                work = 0.0;
-            } else {
-                work -= linklet.remaining;
-                linklet.remaining = 0.0;
-                self.current_index += 1;
+    /// Buys up to `limit` units of a tier, or as many as credits allow.
+    fn purchase_bulk(&mut self, index: usize, limit: Option<u32>) {
+        let name = self.hashpower.tiers[index].name;
+        match self
+            .hashpower
+            .purchase_tier_bulk(index, limit, &mut self.bank)
+        {
+            Some((count, cost)) => {
+                self.remember_action(LastAction::Purchase {
+                    tier: index,
+                    count,
+                    cost,
+                });
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!("Purchased {}× {} for {:.2}₵", count, name, cost),
+                );
+                self.announce_unlock(index, count);
+                self.check_achievements();
             }
+            None => self.push_message(MessageCategory::Error, format!("Can't afford any {}", name)),
         }
     }
 
-    fn is_complete(&self) -> bool {
-        self.current_index >= self.linklets.len()
-    }
-
-    fn finish(self) -> CompletedJob {
-        let finished_at = Utc::now();
-        let duration = self.started_at.elapsed();
-        CompletedJob {
-            job: self.job,
-            finished_at,
-            duration,
+    /// Reveals the next tier when buying `count` of `index` crossed the threshold.
+    fn announce_unlock(&mut self, index: usize, count: u32) {
+        let owned = self.hashpower.tiers[index].owned;
+        let Some(next) = self.hashpower.tiers.get(index + 1) else {
+            return;
+        };
+        if owned >= TIER_UNLOCK_OWNED && owned - count < TIER_UNLOCK_OWNED {
+            let name = next.name;
+            self.push_message(
+                MessageCategory::Info,
+                format!("Unlocked a new tier: {}", name),
+            );
         }
     }
 
-    pub fn completion_ratio(&self) -> f64 {
-        if self.linklets.is_empty() {
-            return 0.0;
+    fn complete_purchase(&mut self, index: usize) {
+        if let Some(cost) = self.hashpower.purchase_tier(index, &mut self.bank) {
+            self.remember_action(LastAction::Purchase {
+                tier: index,
+                count: 1,
+                cost,
+            });
+            self.push_message(
+                MessageCategory::Trade,
+                format!(
+                    "Purchased {} for {:.2}₵",
+                    self.hashpower.tiers[index].name, cost
+                ),
+            );
+            self.announce_unlock(index, 1);
+            self.check_achievements();
+        } else if let Some(tier) = self.hashpower.tiers.get(index) {
+            let message = format!(
+                "Can't buy {}: need {:.2}₵, have {:.2}₵",
+                tier.name,
+                tier.cost_for_next(),
+                self.bank.credits_balance
+            );
+            self.push_message(MessageCategory::Error, message);
         }
-        let total: f64 = self.linklets.iter().map(|l| l.difficulty).sum();
-        let remaining: f64 = self.linklets[self.current_index..]
-            .iter()
-            .map(|l| l.remaining)
-            .sum();
-        ((total - remaining) / total).clamp(0.0, 1.0)
     }
 
-    pub fn status_map(&self) -> Vec<LinkletStatus> {
-        self.linklets
-            .iter()
-            .enumerate()
-            .map(|(idx, linklet)| {
-                if linklet.remaining <= 0.0 {
-                    LinkletStatus::Complete
-                } else if idx == self.current_index {
-                    LinkletStatus::Active
+    fn handle_bank_input(&mut self, key: KeyEvent) {
+        let asset = self.bank_asset;
+        match self.keymap.action(&[Scope::Bank], &key) {
+            Some(Action::SellChain) => self.manual_trade(OrderSide::Sell, 1.0),
+            Some(Action::BuyChain) => self.manual_trade(OrderSide::Buy, 1.0),
+            Some(Action::SellBulk) => self.manual_trade(OrderSide::Sell, 5.0),
+            Some(Action::BuyBulk) => self.manual_trade(OrderSide::Buy, 5.0),
+            Some(Action::Stake) => match self.bank.stake(STAKE_STEP) {
+                Ok(amount) => {
+                    self.last_action = None;
+                    self.push_message(
+                        MessageCategory::Trade,
+                        format!(
+                            "Staked {:.2} ⛓ ({:.2} ⛓ locked for {:.0}s)",
+                            amount, self.bank.staked, STAKE_LOCK_SECS
+                        ),
+                    );
+                }
+                Err(err) => {
+                    self.push_message(MessageCategory::Error, format!("Can't stake: {}", err))
+                }
+            },
+            Some(Action::Unstake) => match self.bank.unstake() {
+                Ok((returned, forfeited)) => {
+                    self.last_action = None;
+                    let message = if forfeited > 0.0 {
+                        format!(
+                            "Unstaked {:.2} ⛓ early, forfeiting {:.2} ⛓",
+                            returned, forfeited
+                        )
+                    } else {
+                        format!("Unstaked {:.2} ⛓", returned)
+                    };
+                    self.push_message(MessageCategory::Trade, message);
+                }
+                Err(err) => {
+                    self.push_message(MessageCategory::Error, format!("Can't unstake: {}", err))
+                }
+            },
+            Some(Action::ToggleAutoSell) => {
+                self.bank.auto_sell_enabled = !self.bank.auto_sell_enabled;
+                let state = if self.bank.auto_sell_enabled {
+                    "on"
                 } else {
-                    LinkletStatus::Pending
+                    "off"
+                };
+                self.push_message(
+                    MessageCategory::Trade,
+                    format!("Auto-sell above {:.0} ⛓ {}", self.bank.auto_sell_cap, state),
+                );
+            }
+            Some(Action::LowerAutoSellCap) => {
+                self.bank.auto_sell_cap = (self.bank.auto_sell_cap - AUTO_SELL_CAP_STEP).max(0.0);
+            }
+            Some(Action::RaiseAutoSellCap) => self.bank.auto_sell_cap += AUTO_SELL_CAP_STEP,
+            Some(Action::Borrow) => self.take_loan(),
+            Some(Action::Repay) => self.repay_loan(),
+            Some(Action::Dump) => self.prepare_manipulation(OrderSide::Sell),
+            Some(Action::Pump) => self.prepare_manipulation(OrderSide::Buy),
+            Some(Action::SwitchAsset) => {
+                self.bank_asset = asset.toggled();
+                self.push_message(
+                    MessageCategory::Info,
+                    format!("Now trading {}", self.bank_asset.label()),
+                );
+            }
+            // Limit orders and alerts only watch the chain market.
+            Some(Action::NewLimitOrder) => {
+                let price = self.ticker.price;
+                self.order_draft = Some(LimitOrder::new(OrderSide::Sell, 10.0, price));
+            }
+            Some(Action::NewAlert) => {
+                let price = self.ticker.price;
+                self.alert_draft = Some(PriceAlert::new(price, price));
+            }
+            Some(Action::ClearAlerts) if !self.ticker.alerts.is_empty() => {
+                self.ticker.alerts.clear();
+                self.push_message(MessageCategory::Info, "Cleared all price alerts");
+            }
+            Some(Action::CancelLimitOrder) => {
+                if let Some(order) = self.bank.limit_orders.pop() {
+                    self.push_message(
+                        MessageCategory::Trade,
+                        format!(
+                            "Cancelled limit {} of {:.1} ⛓ @ {:.2}₵",
+                            order.side.label(),
+                            order.amount,
+                            order.trigger_price
+                        ),
+                    );
                 }
-            })
-            .collect()
-    }
-
-    pub fn remaining_work(&self) -> f64 {
-        self.linklets.iter().map(|l| l.remaining).sum()
-    }
-
-    fn from_save(save: ActiveJobSave) -> Self {
-        let now = Instant::now();
-        let elapsed = Duration::from_secs_f64(save.elapsed_secs.max(0.0));
-        let started_at = now.checked_sub(elapsed).unwrap_or(now);
-        let linklets = save.linklets;
-        let len = linklets.len();
-        Self {
-            job: save.job,
-            linklets,
-            current_index: save.current_index.min(len),
-            started_at,
+            }
+            _ => {}
         }
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub enum LinkletStatus {
-    Pending,
-    Active,
-    Complete,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LinkletProgress {
-    pub difficulty: f64,
-    pub remaining: f64,
-}
 
-impl LinkletProgress {
-    fn new(difficulty: f64) -> Self {
-        Self {
-            difficulty,
-            remaining: difficulty,
+    fn handle_order_entry(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(order) = self.order_draft.take() {
+                    self.push_message(
+                        MessageCategory::Trade,
+                        format!(
+                            "Placed limit {} of {:.1} ⛓ @ {:.2}₵",
+                            order.side.label(),
+                            order.amount,
+                            order.trigger_price
+                        ),
+                    );
+                    self.bank.limit_orders.push(order);
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                self.order_draft = None;
+                return;
+            }
+            _ => {}
         }
-    }
-}
-
-#[derive(Debug)]
-pub struct CompletedJob {
-    pub job: MiningJob,
-    pub finished_at: DateTime<Utc>,
-    pub duration: Duration,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MiningJob {
-    pub name: String,
-    pub rows: usize,
-    pub cols: usize,
-    pub difficulty: f64,
-    pub payout_chain: f64,
-    pub linklet_difficulties: Vec<f64>,
-    pub market_impact: f64,
-    pub lore: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct HashpowerTier {
-    pub name: &'static str,
-    pub base_cost: f64,
-    pub cost_multiplier: f64,
-    pub power: f64,
-    pub owned: u32,
-}
-
-impl HashpowerTier {
-    pub fn cost_for_next(&self) -> f64 {
-        let scaling = self.cost_multiplier.powi(self.owned as i32);
-        self.base_cost * scaling
-    }
-
-    pub fn total_power(&self) -> f64 {
-        self.power * self.owned as f64
-    }
-}
-
-#[derive(Debug)]
-pub struct HashpowerState {
-    pub tiers: Vec<HashpowerTier>,
-    pub selected: usize,
-}
-
-impl Default for HashpowerState {
-    fn default() -> Self {
-        Self {
-            tiers: vec![
-                HashpowerTier {
-                    name: "Processor",
-                    base_cost: 75.0,
-                    cost_multiplier: 1.18,
-                    power: 1.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Server",
-                    base_cost: 420.0,
-                    cost_multiplier: 1.20,
-                    power: 4.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Rack",
-                    base_cost: 2100.0,
-                    cost_multiplier: 1.22,
-                    power: 18.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Lab",
-                    base_cost: 9500.0,
-                    cost_multiplier: 1.24,
-                    power: 65.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Supercomputer",
-                    base_cost: 38000.0,
-                    cost_multiplier: 1.26,
-                    power: 220.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Datacenter",
-                    base_cost: 150000.0,
-                    cost_multiplier: 1.28,
-                    power: 800.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Quantum Array",
-                    base_cost: 620000.0,
-                    cost_multiplier: 1.31,
-                    power: 3000.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Orbital Node",
-                    base_cost: 2_400_000.0,
-                    cost_multiplier: 1.34,
-                    power: 10_500.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Darknet Farm",
-                    base_cost: 8_600_000.0,
-                    cost_multiplier: 1.38,
-                    power: 34_000.0,
-                    owned: 0,
-                },
-                HashpowerTier {
-                    name: "Foundry Core",
-                    base_cost: 28_000_000.0,
-                    cost_multiplier: 1.42,
-                    power: 120_000.0,
-                    owned: 0,
-                },
-            ],
-            selected: 0,
+        let action = self.keymap.action(&[Scope::Draft, Scope::Navigation], &key);
+        let Some(draft) = self.order_draft.as_mut() else {
+            return;
+        };
+        match action {
+            Some(Action::DraftLower) => {
+                draft.trigger_price =
+                    (draft.trigger_price - LIMIT_PRICE_STEP).max(LIMIT_PRICE_STEP);
+            }
+            Some(Action::DraftRaise) => draft.trigger_price += LIMIT_PRICE_STEP,
+            Some(Action::MoveUp) => draft.amount += 1.0,
+            Some(Action::MoveDown) => draft.amount = (draft.amount - 1.0).max(1.0),
+            Some(Action::ToggleOrderSide) => draft.side = draft.side.toggled(),
+            _ => {}
         }
     }
-}
-
-impl HashpowerState {
-    pub fn total_power(&self) -> f64 {
-        self.tiers.iter().map(|tier| tier.total_power()).sum()
-    }
-
-    fn owned_counts(&self) -> Vec<u32> {
-        self.tiers.iter().map(|tier| tier.owned).collect()
-    }
 
-    fn apply_owned(&mut self, owned: &[u32], selected: usize) {
-        for (tier, &amount) in self.tiers.iter_mut().zip(owned.iter()) {
-            tier.owned = amount;
-        }
-        if owned.len() < self.tiers.len() {
-            for tier in self.tiers[owned.len()..].iter_mut() {
-                tier.owned = 0;
+    fn handle_alert_entry(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(alert) = self.alert_draft.take() {
+                    self.push_message(
+                        MessageCategory::Info,
+                        format!(
+                            "Alert set: Chain {} {:.2}₵{}",
+                            alert.direction.label(),
+                            alert.price,
+                            if alert.rearm { " (re-arming)" } else { "" }
+                        ),
+                    );
+                    self.ticker.alerts.push(alert);
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                self.alert_draft = None;
+                return;
             }
+            _ => {}
         }
-        if self.tiers.is_empty() {
-            self.selected = 0;
-        } else {
-            self.selected = selected.min(self.tiers.len() - 1);
+        let price = self.ticker.price;
+        let action = self.keymap.action(&[Scope::Draft], &key);
+        let Some(draft) = self.alert_draft.as_mut() else {
+            return;
+        };
+        match action {
+            Some(Action::DraftLower) => {
+                let target = (draft.price - LIMIT_PRICE_STEP).max(LIMIT_PRICE_STEP);
+                draft.retarget(target, price);
+            }
+            Some(Action::DraftRaise) => {
+                let target = draft.price + LIMIT_PRICE_STEP;
+                draft.retarget(target, price);
+            }
+            Some(Action::ToggleRearm) => draft.rearm = !draft.rearm,
+            _ => {}
         }
     }
 
-    fn select_next(&mut self) {
-        self.selected = (self.selected + 1) % self.tiers.len();
-    }
-
-    fn select_previous(&mut self) {
-        if self.selected == 0 {
-            self.selected = self.tiers.len() - 1;
-        } else {
-            self.selected -= 1;
+    fn handle_goal_entry(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(goal) = self.goal_draft.take() {
+                    self.push_message(
+                        MessageCategory::Info,
+                        format!(
+                            "Goal set: {} {:.0} {}, then {}",
+                            goal.metric.label(),
+                            goal.target,
+                            goal.metric.unit(),
+                            goal.on_reach.label()
+                        ),
+                    );
+                    self.goal = Some(goal);
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                self.goal_draft = None;
+                return;
+            }
+            _ => {}
+        }
+        let action = self.keymap.action(&[Scope::Draft], &key);
+        let Some(draft) = self.goal_draft.as_mut() else {
+            return;
+        };
+        match action {
+            Some(Action::DraftLower) => draft.adjust(-1.0),
+            Some(Action::DraftRaise) => draft.adjust(1.0),
+            Some(Action::ToggleGoalMetric) => {
+                let metric = draft.metric.toggled();
+                let on_reach = draft.on_reach;
+                let mut goal = Goal::above(metric, self.goal_value(metric));
+                goal.on_reach = on_reach;
+                self.goal_draft = Some(goal);
+            }
+            Some(Action::CycleGoalOnReach) => draft.on_reach = draft.on_reach.next(),
+            Some(Action::ClearGoal) => {
+                self.goal_draft = None;
+                if self.goal.take().is_some() {
+                    self.push_message(MessageCategory::Info, "Goal cleared");
+                }
+            }
+            _ => {}
         }
     }
 
-    fn purchase_selected(&mut self, bank: &mut BankState) -> Option<f64> {
-        let tier = &mut self.tiers[self.selected];
-        let cost = tier.cost_for_next();
-        if bank.credits_balance >= cost {
-            bank.credits_balance -= cost;
-            tier.owned += 1;
-            Some(cost)
-        } else {
-            None
+    pub fn goal_value(&self, metric: GoalMetric) -> f64 {
+        match metric {
+            GoalMetric::Chain => self.bank.chain_balance,
+            GoalMetric::NetWorth => self.net_worth(),
         }
     }
 
-    fn selected_name(&self) -> &str {
-        self.tiers[self.selected].name
+    pub fn take_snapshot_request(&mut self) -> bool {
+        std::mem::take(&mut self.snapshot_requested)
     }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BankState {
-    pub chain_balance: f64,
-    pub credits_balance: f64,
-}
 
-impl Default for BankState {
-    fn default() -> Self {
-        Self {
-            chain_balance: 0.0,
-            credits_balance: 100.0,
+    pub fn report_snapshot(&mut self, result: Result<()>) {
+        match result {
+            Ok(()) => self.push_message(
+                MessageCategory::Info,
+                format!("Screen saved to {}", SNAPSHOT_FILE),
+            ),
+            Err(err) => self.push_message(
+                MessageCategory::Error,
+                format!("Snapshot failed: {:#}", err),
+            ),
         }
     }
-}
 
-impl BankState {
-    pub fn sell_chain(&mut self, amount: f64, market_price: f64) -> Option<f64> {
-        if self.chain_balance + 1e-6 < amount {
-            return None;
+    fn ring_bell(&mut self) {
+        if self.config.bell {
+            self.bell_pending = true;
         }
-        let unit_price = market_price * EXCHANGE_SELL_MULTIPLIER;
-        let proceeds = amount * unit_price;
-        self.chain_balance -= amount;
-        self.credits_balance += proceeds;
-        Some(proceeds)
     }
 
-    pub fn buy_chain(&mut self, amount: f64, market_price: f64) -> Option<f64> {
-        let unit_price = market_price * EXCHANGE_BUY_MULTIPLIER;
-        let cost = amount * unit_price;
-        if self.credits_balance + 1e-6 < cost {
-            return None;
-        }
-        self.credits_balance -= cost;
-        self.chain_balance += amount;
-        Some(cost)
+    /// Whether an event asked for the terminal bell since the last call.
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
     }
-}
-
-#[derive(Debug, Default)]
-pub struct LedgerState {
-    pub entries: Vec<LedgerEntry>,
-    pub scroll: usize,
-}
 
-impl LedgerState {
-    fn add_entry(&mut self, entry: LedgerEntry) {
-        self.entries.insert(0, entry);
+    fn check_goal(&mut self) {
+        let Some(goal) = &self.goal else {
+            return;
+        };
+        if self.goal_value(goal.metric) < goal.target {
+            return;
+        }
+        let Some(goal) = self.goal.take() else {
+            return;
+        };
+        self.push_message(
+            MessageCategory::Info,
+            format!(
+                "GOAL REACHED: {} {:.0} {}",
+                goal.metric.label(),
+                goal.target,
+                goal.metric.unit()
+            ),
+        );
+        self.ring_bell();
+        match goal.on_reach {
+            GoalCompletion::Announce => {}
+            GoalCompletion::Pause => {
+                self.enter_pause();
+                self.pause_menu
+                    .set_status(Some("Paused: goal reached.".to_string()));
+            }
+            GoalCompletion::Save => match self.save_game() {
+                Ok(()) => self.push_message(MessageCategory::Info, "Goal snapshot stored to disk."),
+                Err(err) => {
+                    self.push_message(MessageCategory::Error, format!("Save error: {}", err))
+                }
+            },
+        }
     }
 
-    fn scroll_up(&mut self) {
-        if self.scroll > 0 {
-            self.scroll -= 1;
+    fn handle_ledger_input(&mut self, key: KeyEvent) {
+        if self.ledger.filter_editing {
+            self.handle_filter_entry(key);
+            return;
+        }
+        if key.code == KeyCode::Esc && !self.ledger.filter.is_empty() {
+            self.ledger.set_filter(String::new());
+            return;
+        }
+        match self
+            .keymap
+            .action(&[Scope::Navigation, Scope::Ledger], &key)
+        {
+            Some(Action::MoveUp) => self.ledger.scroll_up(),
+            Some(Action::MoveDown) => self.ledger.scroll_down(),
+            Some(Action::SearchLedger) => self.ledger.filter_editing = true,
+            Some(Action::CopyLinkId) => self.copy_selected_link_id(),
+            Some(Action::PageUp) => self.ledger.page_up(self.pane_rects.get().ledger_page),
+            Some(Action::PageDown) => self.ledger.page_down(self.pane_rects.get().ledger_page),
+            Some(Action::JumpNewest) => self.ledger.jump_newest(),
+            Some(Action::JumpOldest) => self.ledger.jump_oldest(),
+            _ => {}
         }
     }
 
-    fn scroll_down(&mut self) {
-        if self.scroll + 1 < self.entries.len() {
-            self.scroll += 1;
+    fn copy_selected_link_id(&mut self) {
+        let Some(id) = self
+            .ledger
+            .visible_entries()
+            .get(self.ledger.scroll)
+            .map(|entry| entry.id.clone())
+        else {
+            self.push_message(MessageCategory::Error, "No ledger entry to copy");
+            return;
+        };
+        match self.copy_to_clipboard(&id) {
+            Ok(()) => self.push_message(
+                MessageCategory::Info,
+                format!("Copied {} to clipboard.", id),
+            ),
+            Err(err) => self.push_message(
+                MessageCategory::Error,
+                format!("Clipboard unavailable ({}); link ID {}", err, id),
+            ),
         }
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct LedgerEntry {
-    pub id: String,
-    pub name: String,
-    pub finished_at: DateTime<Utc>,
-    pub difficulty: f64,
-    pub payout_chain: f64,
-    pub credits_at_completion: f64,
-    pub duration: Duration,
-    pub market_impact: f64,
-}
 
-impl LedgerEntry {
-    pub fn finished_local(&self) -> DateTime<Local> {
-        self.finished_at.with_timezone(&Local)
+    fn copy_to_clipboard(&mut self, text: &str) -> Result<()> {
+        let clipboard = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard,
+            None => self.clipboard.insert(Clipboard::new()?),
+        };
+        clipboard.set_text(text)?;
+        Ok(())
     }
-}
 
-#[derive(Debug)]
-pub struct TickerState {
-    pub price: f64,
-    pub last_delta: f64,
-    pub history: VecDeque<f64>,
-    time_since_update: Duration,
-    update_interval: Duration,
-}
-
-impl TickerState {
-    fn new(initial_price: f64, rng: &mut StdRng) -> Self {
-        let mut history = VecDeque::new();
-        history.push_back(initial_price);
-        Self {
-            price: initial_price,
-            last_delta: 0.0,
-            history,
-            time_since_update: Duration::ZERO,
-            update_interval: Self::roll_interval(rng),
+    fn handle_filter_entry(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let mut filter = self.ledger.filter.clone();
+                filter.push(c);
+                self.ledger.set_filter(filter);
+            }
+            KeyCode::Backspace => {
+                let mut filter = self.ledger.filter.clone();
+                filter.pop();
+                self.ledger.set_filter(filter);
+            }
+            KeyCode::Enter => self.ledger.filter_editing = false,
+            KeyCode::Esc => {
+                self.ledger.filter_editing = false;
+                self.ledger.set_filter(String::new());
+            }
+            _ => {}
         }
     }
 
-    fn tick(&mut self, dt: Duration, rng: &mut StdRng) {
-        self.time_since_update += dt;
-        while self.time_since_update >= self.update_interval {
-            self.time_since_update -= self.update_interval;
-            self.apply_random_walk(rng);
-            self.update_interval = Self::roll_interval(rng);
+    fn handle_pause_input(&mut self, key: KeyEvent) {
+        if self.show_achievements {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                self.show_achievements = false;
+            }
+            return;
+        }
+        if let Some(confirm) = self.pause_menu.confirm {
+            self.handle_pause_confirm(confirm, key);
+            return;
+        }
+        match key.code {
+            KeyCode::Up => self.pause_menu.select_previous(),
+            KeyCode::Down => self.pause_menu.select_next(),
+            KeyCode::Enter => self.activate_pause_selection(),
+            KeyCode::Esc => self.resume(),
+            _ if self.keymap.matches(Action::Pause, &key) => self.resume(),
+            _ => {}
         }
     }
 
-    pub fn seconds_until_update(&self) -> f64 {
-        if self.time_since_update >= self.update_interval {
-            0.0
-        } else {
-            (self.update_interval - self.time_since_update).as_secs_f64()
+    fn handle_pause_confirm(&mut self, confirm: PauseConfirm, key: KeyEvent) {
+        let accepted = match key.code {
+            KeyCode::Enter | KeyCode::Char('y' | 'Y') => true,
+            KeyCode::Char('n' | 'N')
+                if matches!(
+                    confirm,
+                    PauseConfirm::SaveBeforeExit | PauseConfirm::SaveBeforeNewGame
+                ) =>
+            {
+                false
+            }
+            KeyCode::Esc | KeyCode::Char('n' | 'N') => {
+                self.pause_menu.confirm = None;
+                self.pause_menu.set_status(Some("Cancelled.".to_string()));
+                return;
+            }
+            _ => return,
+        };
+        self.pause_menu.confirm = None;
+        match confirm {
+            PauseConfirm::Prestige => {
+                self.prestige();
+                self.pause_menu.set_status(Some(format!(
+                    "Legacy multiplier now ×{:.2}.",
+                    self.legacy_multiplier
+                )));
+            }
+            PauseConfirm::SaveBeforeExit if accepted => match self.save_game() {
+                Ok(()) => self.should_quit = true,
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Save failed, still running: {}", err)));
+                }
+            },
+            PauseConfirm::SaveBeforeExit => self.should_quit = true,
+            PauseConfirm::SaveBeforeNewGame if accepted => match self.save_game() {
+                Ok(()) => self.restart(),
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Save failed, game kept: {}", err)));
+                }
+            },
+            PauseConfirm::SaveBeforeNewGame => self.restart(),
         }
     }
 
-    fn roll_interval(rng: &mut StdRng) -> Duration {
-        Duration::from_secs_f64(rng.gen_range(PRICE_UPDATE_MIN_SECS..=PRICE_UPDATE_MAX_SECS))
+    /// Routes every exit path through the save prompt in the pause menu.
+    fn request_quit(&mut self) {
+        if self.daily.is_some() {
+            self.should_quit = true;
+            return;
+        }
+        self.enter_pause();
+        self.pause_menu.confirm = Some(PauseConfirm::SaveBeforeExit);
     }
 
-    fn apply_random_walk(&mut self, rng: &mut StdRng) {
-        let drift = rng.gen_range(-0.25..0.35);
-        let noise = rng.gen_range(-0.15..0.15);
-        let delta = drift * 0.012 + noise * 0.006;
-        let new_price = (self.price * (1.0 + delta)).max(0.25);
-        self.last_delta = new_price - self.price;
-        self.price = new_price;
-        self.record_price();
+    fn activate_pause_selection(&mut self) {
+        match self.pause_menu.current() {
+            PauseMenuItem::Resume => self.resume(),
+            PauseMenuItem::Save => match self.save_game() {
+                Ok(()) => {
+                    self.pause_menu.set_status(Some("Game saved.".to_string()));
+                    self.push_message(MessageCategory::Info, "Snapshot stored to disk.");
+                }
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Save failed: {}", err)));
+                    self.push_message(MessageCategory::Error, format!("Save error: {}", err));
+                }
+            },
+            PauseMenuItem::Load => match self.load_game() {
+                Ok(LoadSource::Save) => {
+                    self.pause_menu.set_status(Some("Game loaded.".to_string()));
+                    self.push_message(MessageCategory::Info, "Restored state from snapshot.");
+                }
+                Ok(LoadSource::Backup(err)) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Loaded backup {}.", SAVE_BACKUP_FILE)));
+                    self.push_message(
+                        MessageCategory::Error,
+                        format!("Save unreadable ({:#}); restored {}", err, SAVE_BACKUP_FILE),
+                    );
+                }
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Load failed: {}", err)));
+                    self.push_message(MessageCategory::Error, format!("Load error: {}", err));
+                }
+            },
+            PauseMenuItem::Export => match self.export_save() {
+                Ok(status) => self.pause_menu.set_status(Some(status)),
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Export failed: {:#}", err)));
+                }
+            },
+            PauseMenuItem::Import => match self.import_save() {
+                Ok(()) => {
+                    self.pause_menu
+                        .set_status(Some("Export code imported.".to_string()));
+                    self.push_message(MessageCategory::Info, "Restored state from export code.");
+                }
+                Err(err) => {
+                    self.pause_menu
+                        .set_status(Some(format!("Import failed: {:#}", err)));
+                }
+            },
+            PauseMenuItem::Autosave => {
+                self.cycle_autosave_interval();
+                let status = if self.autosave_interval.is_zero() {
+                    "Autosave disabled.".to_string()
+                } else {
+                    format!("Autosaving every {}s.", self.autosave_interval.as_secs())
+                };
+                self.pause_menu.set_status(Some(status));
+            }
+            PauseMenuItem::Theme => {
+                self.theme = self.theme.next();
+                self.pause_menu
+                    .set_status(Some(format!("Theme: {}.", self.theme.label())));
+            }
+            PauseMenuItem::Glyphs => {
+                self.grid_glyphs = self.grid_glyphs.toggled();
+                self.pause_menu
+                    .set_status(Some(format!("Grid glyphs: {}.", self.grid_glyphs.label())));
+            }
+            PauseMenuItem::FocusPause => {
+                self.pause_on_focus_loss = !self.pause_on_focus_loss;
+                let state = if self.pause_on_focus_loss {
+                    "on"
+                } else {
+                    "off"
+                };
+                self.pause_menu
+                    .set_status(Some(format!("Pause when unfocused: {}.", state)));
+            }
+            PauseMenuItem::Achievements => self.show_achievements = true,
+            PauseMenuItem::Prestige => {
+                if self.can_prestige() {
+                    self.pause_menu.confirm = Some(PauseConfirm::Prestige);
+                    self.pause_menu.set_status(None);
+                } else if self.bank.debt > 0.0 {
+                    self.pause_menu.set_status(Some(format!(
+                        "Repay your {:.2}₵ loan before prestiging.",
+                        self.bank.debt
+                    )));
+                } else {
+                    self.pause_menu.set_status(Some(format!(
+                        "Prestige requires {:.0}₵ net worth (have {:.2}₵).",
+                        PRESTIGE_THRESHOLD,
+                        self.net_worth()
+                    )));
+                }
+            }
+            PauseMenuItem::NewGame => {
+                if self.daily.is_some() {
+                    self.pause_menu
+                        .set_status(Some("The daily challenge can't be restarted.".to_string()));
+                } else {
+                    self.pause_menu.confirm = Some(PauseConfirm::SaveBeforeNewGame);
+                    self.pause_menu.set_status(None);
+                }
+            }
+            PauseMenuItem::Quit => self.request_quit(),
+        }
     }
 
-    fn apply_market_nudge(&mut self, impact: f64, payout_chain: f64) -> f64 {
-        let impulse = (impact * payout_chain * 0.01).clamp(-5.0, 5.0);
-        let new_price = (self.price + impulse).max(0.25);
-        let delta = new_price - self.price;
-        self.price = new_price;
-        self.last_delta = delta;
-        self.record_price();
-        delta
+    fn save_game(&mut self) -> Result<()> {
+        self.ensure_not_daily()?;
+        let snapshot = SaveData::from_app(self);
+        let payload = serde_json::to_vec_pretty(&snapshot)?;
+        write_save_atomically(&payload)?;
+        self.autosave_armed = true;
+        Ok(())
     }
 
-    fn record_price(&mut self) {
-        self.history.push_back(self.price);
-        while self.history.len() > 256 {
-            self.history.pop_front();
+    fn ensure_not_daily(&self) -> Result<()> {
+        if self.daily.is_some() {
+            return Err(anyhow!("saves are disabled during the daily challenge"));
         }
+        Ok(())
     }
 
-    fn to_save(&self) -> TickerSave {
-        TickerSave {
-            price: self.price,
-            last_delta: self.last_delta,
-            history: self.history.iter().copied().collect(),
-            time_since_update_secs: self.time_since_update.as_secs_f64(),
-            update_interval_secs: self.update_interval.as_secs_f64(),
+    /// Copies the export code to the clipboard, or writes it to `EXPORT_FILE`
+    /// when no clipboard is available. Returns a status line for the menu.
+    fn export_save(&mut self) -> Result<String> {
+        let code = encode_export(&SaveData::from_app(self))?;
+        match self.copy_to_clipboard(&code) {
+            Ok(()) => Ok(format!("Export code ({} chars) copied.", code.len())),
+            Err(_) => {
+                fs::write(EXPORT_FILE, &code)
+                    .with_context(|| format!("writing {}", EXPORT_FILE))?;
+                Ok(format!(
+                    "No clipboard; export code written to {}.",
+                    EXPORT_FILE
+                ))
+            }
         }
     }
 
-    fn from_save(save: TickerSave) -> Self {
-        let mut history = VecDeque::from(save.history);
-        if history.is_empty() {
-            history.push_back(save.price);
-        }
-        let mut state = Self {
-            price: save.price,
-            last_delta: save.last_delta,
-            history,
-            time_since_update: Duration::from_secs_f64(save.time_since_update_secs.max(0.0)),
-            update_interval: Duration::from_secs_f64(
-                save.update_interval_secs
-                    .clamp(PRICE_UPDATE_MIN_SECS, PRICE_UPDATE_MAX_SECS),
-            ),
+    /// Reads an export code from the clipboard, falling back to `EXPORT_FILE`.
+    fn import_save(&mut self) -> Result<()> {
+        self.ensure_not_daily()?;
+        let clipboard_text = match self.clipboard.as_mut() {
+            Some(clipboard) => clipboard.get_text().ok(),
+            None => Clipboard::new()
+                .ok()
+                .and_then(|mut clipboard| clipboard.get_text().ok()),
         };
-        if state.time_since_update > state.update_interval {
-            state.time_since_update = state.update_interval;
-        }
-        state
-    }
-}
-
-fn generate_job(rng: &mut StdRng) -> MiningJob {
-    const ADJECTIVES: &[&str] = &[
-    "Fractured", "Dim", "Sharded", "Glitched", "Ghost", "Silent", "Echoing", "Cascading",
-    "Prismatic", "Encrypted", "Obsidian", "Harmonic",
-    "Errant", "Flickering", "Hollow", "Recursive", "Spectral", "Jagged", "Volatile", "Drifting",
-    "Lucid", "Null", "Resonant", "Entropic",
-    // new crypto-occult
-    "Forked",
-    "Haunted",
-    "Burned",
-    "Ancestral",
-    "Ledgerbound",
-    "Spectral",
-    "Cursed",
-    "Transient",
-    "Byzantine",
-    "Hallowed",
-    "Orphaned",
-    "Immutable",
-];
-
-    const NOUNS: &[&str] = &[
-    "Segment", "Archive", "Spindle", "Glyph", "Node", "Fragment", "Shard", "Atlas", "Conduit",
-    "Vault", "Kernel", "Beacon",
-    "Ledger", "Relic", "Circuit", "Echo", "Phantom", "Codex", "Anchor", "Relay", "Cipher",
-    "Obelisk", "Tomb", "Vector",
-    // new crypto-occult
-    "Domain",
-    "Epoch",
-    "Oracle",
-    "Burn Address",
-    "Hash",
-    "Mnemonic",
-    "Witness",
-    "Oblivion",
-    "Saint",
-    "Consensus",
-    "Daemon",
-    "Halving",
-];
-    const LORE: &[&str] = &[
-        "Ancient checksum mismatch logs recur in the metadata.",
-        "Ledger note claims this link once belonged to the Archivist.",
-        "The fragment hums at low frequencies when restored.",
-        "OSINT chatter suggests this shard triggered a market spike decades ago.",
-        "An abandoned relay stamped 'BLOCKGRAVE' is encoded in the payload.",
-        "Hidden comment references a broken covenant between miners.",
-        "Telemetry pings from an Orbital Node still reference this checksum.",
-        "A Darknet broker swears this fragment bankrolls quantum rent strikes.",
-        "Foundry Core schematics mark this link as a stabiliser conduit.",
-        "The Null Archivist left an apology packet hidden in the footer.",
-        "Quantum foreman chatter hints at an unfinished ritual encoded here.",
-        "Obsolete syscalls still loop when this node is queried.",
-        "An encrypted diary fragment maps trade routes through dead space.",
-        "The checksum aligns with a forgotten guild’s initiation rites.",
-        "Deep cache inspection reveals timestamps older than the chain itself.",
-        "Data-miners whisper this archive once looped the whole market.",
-        "A phantom transaction ID resurfaces every equinox cycle.",
-        "Broken glyphs resemble prayers to a machine-saint.",
-        "Sector cartographers note this vector bends in non-Euclidean paths.",
-        "The echo of a relay collapse is still trapped in its logs.",
-        "Packet sniffers detect laughter embedded in corrupted frames.",
-        "A genesis block fragment bleeds through the checksum, signed by no known miner.",
-        "Obelisk engravings match hash outputs from a lost forked chain.",
-        "The Null Archivist’s covenant required burning coins to prove loyalty.",
-        "Forked ledgers in this shard contradict every known consensus protocol.",
-        "Ghost miners still broadcast shares to this phantom pool.",
-        "A forgotten saint of code is named in corrupted transaction notes.",
-        "Some claim this anchor wallet drains value every lunar cycle.",
-        "Consensus rites were once chanted in machine-halls around this relic.",
-        "A worm-eaten codex reveals rituals for binding hashes into prayer.",
-        "This beacon’s logs oscillate between laughter and market orders.",
-        "Miners whisper the shard was staked to summon liquidity apparitions.",
-        "An orphan block here encodes a hymn to the Overseer of Chains.",
-        "Hexed glyphs on the relay resemble burn addresses etched in ash.",
-        "An abandoned vault pulses as if waiting for a final signature.",
-        "Market prophets swore this vector predicted halvings centuries early.",
-    ];
-
-    let adjective = ADJECTIVES[rng.gen_range(0..ADJECTIVES.len())];
-    let noun = NOUNS[rng.gen_range(0..NOUNS.len())];
-    let name = format!("{} {}", adjective, noun);
-    let lore = LORE[rng.gen_range(0..LORE.len())].to_string();
-
-    let rows = rng.gen_range(3..=6);
-    let cols = rng.gen_range(4..=8);
-    let count = rows * cols;
-
-    let base_scale = 1.0 + (count as f64 / 36.0);
-    let lognormal = LogNormal::new(0.8, 0.55).unwrap();
-    let mut linklet_difficulties = Vec::with_capacity(count);
-    let mut total_difficulty = 0.0;
-    for _ in 0..count {
-        let sample = lognormal.sample(rng);
-        let difficulty = (sample * base_scale).max(0.4);
-        total_difficulty += difficulty;
-        linklet_difficulties.push(difficulty);
-    }
-
-    let payout_chain = (total_difficulty * rng.gen_range(0.05_f64..0.09_f64)).max(0.8_f64);
-    let market_impact = rng.gen_range(-0.8_f64..1.2_f64);
-
-    MiningJob {
-        name,
-        rows,
-        cols,
-        difficulty: total_difficulty,
-        payout_chain,
-        linklet_difficulties,
-        market_impact,
-        lore,
-    }
-}
-
-fn generate_link_id(job: &MiningJob) -> String {
-    let difficulty_bucket = ((job.difficulty / 220.0).clamp(0.0, 1.0) * 15.0).floor() as u8;
-    let size_bucket = ((job.linklet_difficulties.len() as f64).log2().floor()) as u8;
-    let body = nanoid!(6, NANO_ALPHABET);
-    let id_core = format!("L{:X}{:X}-{}", difficulty_bucket, size_bucket.min(15), body);
-    let hash = blake3::hash(id_core.as_bytes());
-    let nibble = (hash.as_bytes()[0] >> 4) as u32;
-    let checksum = std::char::from_digit(nibble, 16)
-        .unwrap_or('0')
-        .to_ascii_uppercase();
-    format!("{}-{}", id_core, checksum)
-}
-
-pub fn format_duration(duration: Duration) -> String {
-    let secs = duration.as_secs();
-    let millis = duration.subsec_millis();
-    if secs >= 3600 {
-        let hours = secs / 3600;
-        let minutes = (secs % 3600) / 60;
-        format!("{:02}h{:02}m", hours, minutes)
-    } else if secs >= 60 {
-        let minutes = secs / 60;
-        let seconds = secs % 60;
-        format!("{:02}m{:02}s", minutes, seconds)
-    } else {
-        format!("{:02}.{:03}s", secs, millis)
+        let code = match clipboard_text {
+            Some(text) if text.trim_start().starts_with(EXPORT_PREFIX) => text,
+            _ => fs::read_to_string(EXPORT_FILE).with_context(|| {
+                format!("no export code on the clipboard or in {}", EXPORT_FILE)
+            })?,
+        };
+        let snapshot = decode_export(&code)?;
+        snapshot.apply(self)?;
+        self.last_action = None;
+        self.autosave_armed = true;
+        Ok(())
     }
-}
 
-pub fn format_price_delta(delta: f64) -> String {
-    if delta.abs() < 0.005 {
-        "±0.00".to_string()
-    } else if delta >= 0.0 {
-        format!("+{:.2}", delta)
-    } else {
-        format!("{:.2}", delta)
+    /// Loads `SAVE_FILE`, falling back to the backup when it can't be read.
+    fn load_game(&mut self) -> Result<LoadSource> {
+        self.ensure_not_daily()?;
+        let (snapshot, source) = match read_save_file(SAVE_FILE) {
+            Ok(snapshot) => (snapshot, LoadSource::Save),
+            Err(err) if Path::new(SAVE_BACKUP_FILE).exists() => {
+                let snapshot = read_save_file(SAVE_BACKUP_FILE)
+                    .with_context(|| format!("{:#}; backup also unreadable", err))?;
+                (snapshot, LoadSource::Backup(err))
+            }
+            Err(err) => return Err(err),
+        };
+        snapshot.apply(self)?;
+        self.last_action = None;
+        self.autosave_armed = true;
+        Ok(source)
     }
 }
 
-pub fn format_relings(power: f64) -> String {
-    const UNITS: [(&str, f64); 5] = [
-        ("Rl/s", 1.0),
-        ("kRl/s", 1_000.0),
-        ("MRl/s", 1_000_000.0),
-        ("GRl/s", 1_000_000_000.0),
-        ("TRl/s", 1_000_000_000_000.0),
-    ];
-    let mut value = power;
-    let mut idx = 0usize;
-    while value >= 1000.0 && idx + 1 < UNITS.len() {
-        value /= 1000.0;
-        idx += 1;
-    }
-    format!("{:.2} {}", value, UNITS[idx].0)
+fn prestige_gain(net_worth: f64) -> f64 {
+    PRESTIGE_GAIN_SCALE * (net_worth.max(0.0) / PRESTIGE_THRESHOLD).sqrt()
 }
 
-#[derive(Serialize, Deserialize)]
-struct SaveData {
-    focus: PaneFocus,
-    mining: MiningSave,
-    hashpower_owned: Vec<u32>,
-    hashpower_selected: usize,
-    bank: BankState,
-    ledger: Vec<LedgerEntrySave>,
-    ledger_scroll: usize,
-    ticker: TickerSave,
-    messages: Vec<String>,
+/// A seeded run on default settings with simulated time and autosave off.
+#[cfg(test)]
+pub(super) fn test_app(seed: u64, preset: Preset) -> App {
+    let mut app = App::fresh(Some(seed), preset, GameConfig::default(), Keymap::default());
+    app.clock = Box::new(SimulatedClock::starting_at(chrono::DateTime::UNIX_EPOCH));
+    app.autosave_interval = Duration::ZERO;
+    app
 }
 
-impl SaveData {
-    fn from_app(app: &App) -> Self {
-        Self {
-            focus: app.focus,
-            mining: app.mining.to_save(),
-            hashpower_owned: app.hashpower.owned_counts(),
-            hashpower_selected: app.hashpower.selected,
-            bank: app.bank.clone(),
-            ledger: app
-                .ledger
-                .entries
-                .iter()
-                .map(LedgerEntrySave::from_entry)
-                .collect(),
-            ledger_scroll: app.ledger.scroll,
-            ticker: app.ticker.to_save(),
-            messages: app.messages.iter().cloned().collect(),
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::config::GridBounds;
+
+    /// A chain contract whose linklets each take a whole number of seconds at `power`.
+    fn timed_job(power: f64, linklet_secs: &[f64]) -> MiningJob {
+        let linklet_difficulties: Vec<f64> = linklet_secs.iter().map(|secs| secs * power).collect();
+        MiningJob {
+            name: "Test Vector".to_string(),
+            rows: 1,
+            cols: linklet_difficulties.len(),
+            difficulty: linklet_difficulties.iter().sum(),
+            payout_chain: 12.5,
+            linklet_difficulties,
+            market_impact: 0.0,
+            lore: String::new(),
+            rarity: JobRarity::Common,
+            asset: Asset::Chain,
+            expires_in: CONTRACT_TTL_MAX_SECS,
         }
     }
 
-    fn apply(self, app: &mut App) -> Result<()> {
-        app.focus = self.focus;
-        app.mining.apply_save(self.mining);
-        app.hashpower
-            .apply_owned(&self.hashpower_owned, self.hashpower_selected);
-        app.bank = self.bank;
-        app.ledger.entries = self
-            .ledger
-            .into_iter()
-            .map(LedgerEntrySave::into_entry)
-            .collect::<Result<Vec<_>>>()?;
-        if app.ledger.entries.is_empty() {
-            app.ledger.scroll = 0;
-        } else {
-            app.ledger.scroll = self.ledger_scroll.min(app.ledger.entries.len() - 1);
+    #[test]
+    fn job_mined_over_fixed_ticks_lands_exact_payout_and_duration() {
+        let mut app = test_app(7, Preset::Normal);
+        let power = app.hashpower.effective_power();
+        assert!(power > 0.0);
+        app.mining.strategy = WorkStrategy::Sequential;
+        app.mining.active_job = Some(ActiveJob::new(timed_job(power, &[1.0, 1.0, 2.0])));
+        let chain_before = app.bank.chain_balance;
+
+        for _ in 0..3 {
+            app.on_tick(Duration::from_secs(1));
         }
-        app.ticker = TickerState::from_save(self.ticker);
-        app.messages = VecDeque::from(self.messages);
-        while app.messages.len() > MAX_MESSAGES {
-            app.messages.pop_back();
+        assert!(app.ledger.entries.is_empty());
+        app.on_tick(Duration::from_secs(1));
+
+        assert_eq!(app.ledger.entries.len(), 1);
+        let entry = &app.ledger.entries[0];
+        assert_eq!(entry.payout_chain, 12.5);
+        assert_eq!(entry.duration, Duration::from_secs(4));
+        assert_eq!(
+            entry.finished_at,
+            DateTime::UNIX_EPOCH + Duration::from_secs(4)
+        );
+        assert_eq!(app.bank.chain_balance, chain_before + 12.5);
+    }
+
+    #[test]
+    fn paused_ticks_leave_simulated_time_and_work_alone() {
+        let mut app = test_app(7, Preset::Normal);
+        let power = app.hashpower.effective_power();
+        app.mining.active_job = Some(ActiveJob::new(timed_job(power, &[2.0])));
+        app.paused = true;
+        app.on_tick(Duration::from_secs(5));
+        assert_eq!(app.clock.now(), DateTime::UNIX_EPOCH);
+
+        app.paused = false;
+        app.on_tick(Duration::from_secs(2));
+        let entry = &app.ledger.entries[0];
+        assert_eq!(entry.duration, Duration::from_secs(2));
+        assert_eq!(
+            entry.finished_at,
+            DateTime::UNIX_EPOCH + Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn alert_draft_tracks_chain_price_while_trading_shards() {
+        let mut app = test_app(7, Preset::Normal);
+        app.bank_asset = Asset::Shards;
+        app.focus = PaneFocus::Bank;
+        app.on_key(KeyEvent::from(KeyCode::Char('a')));
+        let draft = app.alert_draft.as_ref().unwrap();
+        assert_eq!(draft.price, app.ticker.price);
+    }
+
+    fn dump(app: &mut App, amount: f64) {
+        let fill_price = app.ticker.price;
+        app.execute_manipulation(PendingManipulation {
+            side: OrderSide::Sell,
+            asset: Asset::Chain,
+            amount,
+            shock: -0.2,
+            fill_price,
+        });
+    }
+
+    #[test]
+    fn dump_proceeds_pay_down_the_loan() {
+        let mut app = test_app(7, Preset::Normal);
+        app.bank.chain_balance = 10.0;
+        app.bank.debt = 1_000.0;
+        let credits_before = app.bank.credits_balance;
+        dump(&mut app, 10.0);
+        let proceeds = app.stats.credits_from_sales;
+        assert!(proceeds > 0.0);
+        let repaid = proceeds * LOAN_AUTO_REPAY_SHARE;
+        assert!((app.bank.debt - (1_000.0 - repaid)).abs() < 1e-9);
+        assert!((app.bank.credits_balance - (credits_before + proceeds - repaid)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dump_counts_towards_best_sale_ratio() {
+        let mut app = test_app(7, Preset::Normal);
+        app.ticker.history = std::iter::repeat_n(10.0, LONG_MA_WINDOW - 1).collect();
+        app.ticker.price = 40.0;
+        app.ticker.history.push_back(40.0);
+        app.bank.chain_balance = 10.0;
+        dump(&mut app, 10.0);
+        assert_eq!(app.stats.best_sale_ratio, 40.0 / 11.0);
+        assert!(app.achievements.contains(&AchievementId::SpikeSeller));
+    }
+
+    #[test]
+    fn buy_max_asks_before_spending_the_balance() {
+        let mut app = test_app(7, Preset::Normal);
+        app.focus = PaneFocus::Hashpower;
+        app.hashpower.selected = 0;
+        app.bank.credits_balance = 10_000.0;
+        let owned = app.hashpower.tiers[0].owned;
+        let credits = app.bank.credits_balance;
+        app.on_key(KeyEvent::from(KeyCode::Char('m')));
+        let pending = app.pending_purchase.unwrap();
+        assert!(pending.count > 1);
+        assert_eq!(app.bank.credits_balance, credits);
+
+        app.on_key(KeyEvent::from(KeyCode::Char('y')));
+        assert!(app.pending_purchase.is_none());
+        assert_eq!(app.hashpower.tiers[0].owned, owned + pending.count);
+        assert_eq!(app.bank.credits_balance, credits - pending.cost);
+    }
+
+    #[test]
+    fn open_order_draft_captures_global_keys() {
+        let mut app = test_app(7, Preset::Normal);
+        app.focus = PaneFocus::Bank;
+        app.on_key(KeyEvent::from(KeyCode::Char('o')));
+        let side = app.order_draft.as_ref().unwrap().side;
+        for code in [KeyCode::Tab, KeyCode::Char('1'), KeyCode::Char('q')] {
+            app.on_key(KeyEvent::from(code));
         }
-        Ok(())
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-struct MiningSave {
-    available_jobs: Vec<MiningJob>,
-    selected_job: usize,
-    active_job: Option<ActiveJobSave>,
-}
-
-#[derive(Serialize, Deserialize)]
-struct ActiveJobSave {
-    job: MiningJob,
-    linklets: Vec<LinkletProgress>,
-    current_index: usize,
-    elapsed_secs: f64,
-}
-
-impl ActiveJobSave {
-    fn from_active(active: &ActiveJob) -> Self {
-        Self {
-            job: active.job.clone(),
-            linklets: active.linklets.clone(),
-            current_index: active.current_index,
-            elapsed_secs: active.started_at.elapsed().as_secs_f64(),
+        assert_eq!(app.focus, PaneFocus::Bank);
+        assert!(!app.paused);
+        app.on_key(KeyEvent::from(KeyCode::Char('s')));
+        assert_ne!(app.order_draft.as_ref().unwrap().side, side);
+        app.on_key(KeyEvent::from(KeyCode::Esc));
+        assert!(app.order_draft.is_none());
+    }
+
+    #[test]
+    fn goal_draft_edits_through_the_draft_keymap() {
+        let mut app = test_app(7, Preset::Normal);
+        app.goal = Some(Goal::above(GoalMetric::Chain, 0.0));
+        app.on_key(KeyEvent::from(app.keymap.key(Action::SetGoal)));
+        let draft = app.goal_draft.clone().unwrap();
+        app.on_key(KeyEvent::from(app.keymap.key(Action::DraftRaise)));
+        assert!(app.goal_draft.as_ref().unwrap().target > draft.target);
+        app.on_key(KeyEvent::from(app.keymap.key(Action::CycleGoalOnReach)));
+        assert_ne!(app.goal_draft.as_ref().unwrap().on_reach, draft.on_reach);
+        app.on_key(KeyEvent::from(app.keymap.key(Action::ToggleGoalMetric)));
+        assert_ne!(app.goal_draft.as_ref().unwrap().metric, draft.metric);
+        app.on_key(KeyEvent::from(app.keymap.key(Action::ClearGoal)));
+        assert!(app.goal_draft.is_none());
+        assert!(app.goal.is_none());
+    }
+
+    #[test]
+    fn undoing_a_purchase_of_rigs_no_longer_owned_explains_why() {
+        let mut app = test_app(7, Preset::Normal);
+        app.last_action = Some(LastAction::Purchase {
+            tier: 0,
+            count: app.hashpower.tiers[0].owned + 1,
+            cost: 50.0,
+        });
+        let credits = app.bank.credits_balance;
+        app.undo_last_action();
+        assert_eq!(app.bank.credits_balance, credits);
+        assert_eq!(app.messages[0].category, MessageCategory::Error);
+        assert!(app.last_action.is_some());
+    }
+
+    #[test]
+    fn undo_expires_once_the_window_passes() {
+        let mut app = test_app(7, Preset::Normal);
+        app.bank.credits_balance = 1_000.0;
+        app.manual_trade(OrderSide::Buy, 1.0);
+        app.on_tick(Duration::from_secs_f64(UNDO_WINDOW_SECS));
+        let chain = app.bank.chain_balance;
+        app.undo_last_action();
+        assert_eq!(app.bank.chain_balance, chain);
+        assert!(app.last_action.is_none());
+    }
+
+    #[test]
+    fn undone_buy_keeps_the_flat_fee() {
+        let mut app = test_app(7, Preset::Normal);
+        app.bank.credits_balance = 1_000.0;
+        app.manual_trade(OrderSide::Buy, 1.0);
+        let fees = app.bank.total_fees_paid;
+        app.undo_last_action();
+        assert!((app.bank.credits_balance - (1_000.0 - EXCHANGE_FLAT_FEE)).abs() < 1e-9);
+        assert_eq!(app.bank.total_fees_paid, fees);
+    }
+
+    #[test]
+    fn staking_clears_the_pending_undo() {
+        let mut app = test_app(7, Preset::Normal);
+        app.bank.credits_balance = 1_000.0;
+        app.bank.chain_balance = 100.0;
+        app.manual_trade(OrderSide::Buy, 1.0);
+        app.focus = PaneFocus::Bank;
+        let stake = app.keymap.key(Action::Stake);
+        app.on_key(KeyEvent::from(stake));
+        assert!(app.bank.staked > 0.0);
+        assert!(app.last_action.is_none());
+    }
+
+    #[test]
+    fn daily_run_ignores_a_non_default_config() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        let custom = GameConfig {
+            job_pool_size: 7,
+            grid: GridBounds {
+                min_rows: 5,
+                max_rows: 8,
+                min_cols: 6,
+                max_cols: 10,
+            },
+            spread: 0.08,
+            market_depth: 0.0,
+            price_history: 90,
+            savings_interest: false,
+            ..GameConfig::default()
+        };
+        let mut shared = App::fresh_daily(date, GameConfig::default(), Keymap::default());
+        let mut own = App::fresh_daily(date, custom, Keymap::default());
+        for _ in 0..50 {
+            shared.on_tick(Duration::from_millis(200));
+            own.on_tick(Duration::from_millis(200));
         }
+        let names = |app: &App| {
+            app.mining
+                .available_jobs
+                .iter()
+                .map(|job| job.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&shared), names(&own));
+        assert_eq!(shared.ticker.history, own.ticker.history);
+        assert_eq!(shared.bank.spread, own.bank.spread);
     }
 }
-
-#[derive(Serialize, Deserialize)]
-struct LedgerEntrySave {
-    id: String,
-    name: String,
-    finished_at_ms: i64,
-    difficulty: f64,
-    payout_chain: f64,
-    credits_at_completion: f64,
-    duration_secs: f64,
-    market_impact: f64,
-}
-
-impl LedgerEntrySave {
-    fn from_entry(entry: &LedgerEntry) -> Self {
-        Self {
-            id: entry.id.clone(),
-            name: entry.name.clone(),
-            finished_at_ms: entry.finished_at.timestamp_millis(),
-            difficulty: entry.difficulty,
-            payout_chain: entry.payout_chain,
-            credits_at_completion: entry.credits_at_completion,
-            duration_secs: entry.duration.as_secs_f64(),
-            market_impact: entry.market_impact,
-        }
-    }
-
-    fn into_entry(self) -> Result<LedgerEntry> {
-        let finished_at = DateTime::<Utc>::from_timestamp_millis(self.finished_at_ms)
-            .ok_or_else(|| anyhow!("invalid timestamp in save data"))?;
-        Ok(LedgerEntry {
-            id: self.id,
-            name: self.name,
-            finished_at,
-            difficulty: self.difficulty,
-            payout_chain: self.payout_chain,
-            credits_at_completion: self.credits_at_completion,
-            duration: Duration::from_secs_f64(self.duration_secs.max(0.0)),
-            market_impact: self.market_impact,
-        })
-    }
-}
-
-#[derive(Serialize, Deserialize)]
-struct TickerSave {
-    price: f64,
-    last_delta: f64,
-    history: Vec<f64>,
-    time_since_update_secs: f64,
-    update_interval_secs: f64,
-}
@@ -0,0 +1,556 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::preset::Preset;
+
+use super::ticker::TickerState;
+
+/// Credits of order value that would move a price all the way; see `trade_impact`.
+pub const DEFAULT_MARKET_DEPTH: f64 = 50_000.0;
+const MAX_TRADE_IMPACT: f64 = 0.5;
+/// Hourly compound rate paid on idle credits: enough to reward holding, not to farm.
+const DEFAULT_SAVINGS_RATE_PER_HOUR: f64 = 0.02;
+/// Flat exchange fee charged on every trade on top of the spread.
+pub const EXCHANGE_FLAT_FEE: f64 = 0.10;
+/// Fraction above market paid on buys and below market received on sells.
+pub const DEFAULT_EXCHANGE_SPREAD: f64 = 0.01;
+/// Credits advanced per borrow keypress.
+pub const LOAN_STEP: f64 = 250.0;
+/// Hourly compound rate charged on outstanding debt.
+pub const LOAN_RATE_PER_HOUR: f64 = 0.12;
+/// Credit line available before any hashpower is counted.
+pub(super) const LOAN_BASE_LIMIT: f64 = 500.0;
+/// Extra credit line per unit of hashpower, i.e. borrowing against future mining.
+pub(super) const LOAN_LIMIT_PER_POWER: f64 = 25.0;
+/// Share of every sale's proceeds withheld to pay down debt.
+pub const LOAN_AUTO_REPAY_SHARE: f64 = 0.25;
+const DEFAULT_AUTO_SELL_CAP: f64 = 100.0;
+pub const AUTO_SELL_CAP_STEP: f64 = 10.0;
+/// Chain moved into the stake per keypress.
+pub const STAKE_STEP: f64 = 10.0;
+/// Hourly compound yield paid in chain on the staked amount.
+pub const STAKING_RATE_PER_HOUR: f64 = 0.05;
+/// Staking again restarts this lock; unstaking before it ends forfeits a share.
+pub const STAKE_LOCK_SECS: f64 = 300.0;
+pub const EARLY_UNSTAKE_PENALTY: f64 = 0.05;
+
+/// A mined, tradeable resource with its own market.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Asset {
+    #[default]
+    Chain,
+    Shards,
+}
+
+impl Asset {
+    pub fn label(self) -> &'static str {
+        match self {
+            Asset::Chain => "Chain",
+            Asset::Shards => "Shards",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Asset::Chain => "⛓",
+            Asset::Shards => "◆",
+        }
+    }
+
+    pub(super) fn toggled(self) -> Self {
+        match self {
+            Asset::Chain => Asset::Shards,
+            Asset::Shards => Asset::Chain,
+        }
+    }
+
+    /// Shards trade far cheaper than chain, so contracts pay out more of them.
+    pub(super) fn payout_scale(self) -> f64 {
+        match self {
+            Asset::Chain => 1.0,
+            Asset::Shards => 3.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AssetQuotes {
+    pub chain: f64,
+    pub shards: f64,
+}
+
+impl AssetQuotes {
+    pub fn price(&self, asset: Asset) -> f64 {
+        match asset {
+            Asset::Chain => self.chain,
+            Asset::Shards => self.shards,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl OrderSide {
+    pub fn label(self) -> &'static str {
+        match self {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        }
+    }
+
+    pub(super) fn toggled(self) -> Self {
+        match self {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitOrder {
+    pub side: OrderSide,
+    pub amount: f64,
+    pub trigger_price: f64,
+    #[serde(default)]
+    warned: bool,
+}
+
+impl LimitOrder {
+    pub(super) fn new(side: OrderSide, amount: f64, trigger_price: f64) -> Self {
+        Self {
+            side,
+            amount,
+            trigger_price,
+            warned: false,
+        }
+    }
+
+    fn is_triggered(&self, market_price: f64) -> bool {
+        match self.side {
+            OrderSide::Sell => market_price >= self.trigger_price,
+            OrderSide::Buy => market_price <= self.trigger_price,
+        }
+    }
+}
+
+/// Outcome of a market order: credits received or paid, the average price
+/// filled at before spread and fee, and how far it moved the market.
+#[derive(Debug, Clone, Copy)]
+pub struct MarketFill {
+    pub credits: f64,
+    pub average_price: f64,
+    pub price_move: f64,
+}
+
+pub(super) enum LimitOrderEvent {
+    Filled { order: LimitOrder, fill: MarketFill },
+    Blocked { order: LimitOrder },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankState {
+    pub chain_balance: f64,
+    #[serde(default)]
+    pub shard_balance: f64,
+    pub credits_balance: f64,
+    #[serde(default)]
+    pub limit_orders: Vec<LimitOrder>,
+    #[serde(default = "default_savings_rate")]
+    pub savings_rate_per_hour: f64,
+    #[serde(default)]
+    pub interest_earned: f64,
+    #[serde(default)]
+    pub total_fees_paid: f64,
+    /// Seconds until another pump or dump is allowed.
+    #[serde(default)]
+    pub manipulation_cooldown: f64,
+    /// Outstanding loan principal plus accrued interest.
+    #[serde(default)]
+    pub debt: f64,
+    #[serde(default)]
+    pub loan_interest_paid: f64,
+    /// Chain locked in staking; it earns yield but can't be traded.
+    #[serde(default)]
+    pub staked: f64,
+    #[serde(default)]
+    pub staking_earned: f64,
+    /// Seconds until unstaking is penalty-free.
+    #[serde(default)]
+    pub stake_lock: f64,
+    #[serde(default)]
+    pub auto_sell_enabled: bool,
+    /// Chain holdings above this are sold at market every tick while enabled.
+    #[serde(default = "default_auto_sell_cap")]
+    pub auto_sell_cap: f64,
+    /// Set from the config for new games; a save keeps the spread it started with.
+    #[serde(default = "default_exchange_spread")]
+    pub spread: f64,
+}
+
+fn default_exchange_spread() -> f64 {
+    DEFAULT_EXCHANGE_SPREAD
+}
+
+fn default_auto_sell_cap() -> f64 {
+    DEFAULT_AUTO_SELL_CAP
+}
+
+fn default_savings_rate() -> f64 {
+    DEFAULT_SAVINGS_RATE_PER_HOUR
+}
+
+impl Default for BankState {
+    fn default() -> Self {
+        Self {
+            chain_balance: 0.0,
+            shard_balance: 0.0,
+            credits_balance: 100.0,
+            limit_orders: Vec::new(),
+            savings_rate_per_hour: DEFAULT_SAVINGS_RATE_PER_HOUR,
+            interest_earned: 0.0,
+            total_fees_paid: 0.0,
+            manipulation_cooldown: 0.0,
+            debt: 0.0,
+            loan_interest_paid: 0.0,
+            staked: 0.0,
+            staking_earned: 0.0,
+            stake_lock: 0.0,
+            auto_sell_enabled: false,
+            auto_sell_cap: DEFAULT_AUTO_SELL_CAP,
+            spread: DEFAULT_EXCHANGE_SPREAD,
+        }
+    }
+}
+
+impl BankState {
+    pub(super) fn starting(preset: Preset, spread: f64) -> Self {
+        Self {
+            credits_balance: preset.starting_credits(),
+            spread,
+            ..Self::default()
+        }
+    }
+
+    pub fn buy_multiplier(&self) -> f64 {
+        1.0 + self.spread
+    }
+
+    pub fn sell_multiplier(&self) -> f64 {
+        1.0 - self.spread
+    }
+
+    /// Compounds loan interest and, when `savings` is on, the savings rate over
+    /// `secs` of play. Balances at or below zero earn nothing.
+    pub(super) fn accrue_interest(&mut self, secs: f64, savings: bool) {
+        if self.debt > 0.0 {
+            let charge = self.debt * ((1.0 + LOAN_RATE_PER_HOUR).powf(secs / 3600.0) - 1.0);
+            self.debt += charge;
+            self.loan_interest_paid += charge;
+        }
+        if !savings || self.credits_balance <= 0.0 || self.savings_rate_per_hour <= 0.0 {
+            return;
+        }
+        let growth = (1.0 + self.savings_rate_per_hour).powf(secs / 3600.0) - 1.0;
+        let interest = self.credits_balance * growth;
+        self.credits_balance += interest;
+        self.interest_earned += interest;
+    }
+
+    pub(super) fn accrue_staking(&mut self, secs: f64) {
+        self.stake_lock = (self.stake_lock - secs).max(0.0);
+        if self.staked <= 0.0 {
+            return;
+        }
+        let reward = self.staked * ((1.0 + STAKING_RATE_PER_HOUR).powf(secs / 3600.0) - 1.0);
+        self.staked += reward;
+        self.staking_earned += reward;
+    }
+
+    /// Moves up to `amount` chain into the stake and restarts the lock.
+    pub(super) fn stake(&mut self, amount: f64) -> Result<f64> {
+        let amount = amount.min(self.chain_balance);
+        if amount < 0.01 {
+            return Err(anyhow!("no chain to stake"));
+        }
+        self.chain_balance -= amount;
+        self.staked += amount;
+        self.stake_lock = STAKE_LOCK_SECS;
+        Ok(amount)
+    }
+
+    /// Returns the whole stake to the chain balance, minus the early-exit
+    /// penalty while the lock is running. Yields `(returned, forfeited)`.
+    pub(super) fn unstake(&mut self) -> Result<(f64, f64)> {
+        if self.staked <= 0.0 {
+            return Err(anyhow!("nothing staked"));
+        }
+        let forfeited = if self.stake_lock > 0.0 {
+            self.staked * EARLY_UNSTAKE_PENALTY
+        } else {
+            0.0
+        };
+        let returned = self.staked - forfeited;
+        self.chain_balance += returned;
+        self.staked = 0.0;
+        self.stake_lock = 0.0;
+        Ok((returned, forfeited))
+    }
+
+    /// Advances `amount` credits as long as total debt stays within `limit`.
+    pub(super) fn borrow(&mut self, amount: f64, limit: f64) -> Result<f64> {
+        let headroom = limit - self.debt;
+        if headroom < 1.0 {
+            return Err(anyhow!(
+                "already owe {:.2}₵ of a {:.0}₵ limit",
+                self.debt,
+                limit
+            ));
+        }
+        let amount = amount.min(headroom);
+        self.debt += amount;
+        self.credits_balance += amount;
+        Ok(amount)
+    }
+
+    /// Pays down debt with up to `amount` credits on hand; returns what was paid.
+    pub(super) fn repay(&mut self, amount: f64) -> f64 {
+        let paid = amount.min(self.debt).min(self.credits_balance).max(0.0);
+        self.debt -= paid;
+        self.credits_balance -= paid;
+        if self.debt < 1e-6 {
+            self.debt = 0.0;
+        }
+        paid
+    }
+
+    pub fn balance(&self, asset: Asset) -> f64 {
+        match asset {
+            Asset::Chain => self.chain_balance,
+            Asset::Shards => self.shard_balance,
+        }
+    }
+
+    pub(super) fn balance_mut(&mut self, asset: Asset) -> &mut f64 {
+        match asset {
+            Asset::Chain => &mut self.chain_balance,
+            Asset::Shards => &mut self.shard_balance,
+        }
+    }
+
+    pub fn sell(&mut self, asset: Asset, amount: f64, market_price: f64) -> Result<f64> {
+        let held = self.balance(asset);
+        if held + 1e-6 < amount {
+            return Err(anyhow!(
+                "not enough {} to sell {:.1} (have {:.2})",
+                asset.label().to_lowercase(),
+                amount,
+                held
+            ));
+        }
+        let unit_price = market_price * self.sell_multiplier();
+        let proceeds = amount * unit_price - EXCHANGE_FLAT_FEE;
+        if proceeds <= 0.0 {
+            return Err(anyhow!(
+                "{:.1} {} wouldn't cover the {:.2}₵ fee",
+                amount,
+                asset.symbol(),
+                EXCHANGE_FLAT_FEE
+            ));
+        }
+        *self.balance_mut(asset) -= amount;
+        self.credits_balance += proceeds;
+        self.total_fees_paid += EXCHANGE_FLAT_FEE;
+        Ok(proceeds)
+    }
+
+    /// Fills every triggered order the balances can cover. Orders that trigger
+    /// but can't be covered stay pending and are reported once.
+    pub(super) fn execute_limit_orders(
+        &mut self,
+        ticker: &mut TickerState,
+        depth: f64,
+    ) -> Vec<LimitOrderEvent> {
+        let mut events = Vec::new();
+        let mut pending = Vec::with_capacity(self.limit_orders.len());
+        for mut order in std::mem::take(&mut self.limit_orders) {
+            // Each fill walks the price, so later orders see where earlier ones left it.
+            if !order.is_triggered(ticker.price) {
+                order.warned = false;
+                pending.push(order);
+                continue;
+            }
+            let fill = self
+                .market_order(ticker, Asset::Chain, order.side, order.amount, depth)
+                .ok();
+            match fill {
+                Some(fill) => events.push(LimitOrderEvent::Filled { order, fill }),
+                None => {
+                    if !order.warned {
+                        order.warned = true;
+                        events.push(LimitOrderEvent::Blocked {
+                            order: order.clone(),
+                        });
+                    }
+                    pending.push(order);
+                }
+            }
+        }
+        self.limit_orders = pending;
+        events
+    }
+
+    /// Fills at the average of the prices the order walks through, then leaves
+    /// `ticker` where the order ended.
+    pub(super) fn market_order(
+        &mut self,
+        ticker: &mut TickerState,
+        asset: Asset,
+        side: OrderSide,
+        amount: f64,
+        depth: f64,
+    ) -> Result<MarketFill> {
+        let price = ticker.price;
+        let impact = trade_impact(amount * price, depth);
+        let direction = match side {
+            OrderSide::Sell => -1.0,
+            OrderSide::Buy => 1.0,
+        };
+        let average_price = price * (1.0 + direction * impact / 2.0);
+        let credits = match side {
+            OrderSide::Sell => self.sell(asset, amount, average_price)?,
+            OrderSide::Buy => self.buy(asset, amount, average_price)?,
+        };
+        let price_move = ticker.shift_price(price * direction * impact);
+        Ok(MarketFill {
+            credits,
+            average_price,
+            price_move,
+        })
+    }
+
+    pub fn buy(&mut self, asset: Asset, amount: f64, market_price: f64) -> Result<f64> {
+        let unit_price = market_price * self.buy_multiplier();
+        let cost = amount * unit_price + EXCHANGE_FLAT_FEE;
+        if self.credits_balance + 1e-6 < cost {
+            return Err(anyhow!(
+                "need {:.2}₵, have {:.2}₵",
+                cost,
+                self.credits_balance
+            ));
+        }
+        self.credits_balance -= cost;
+        *self.balance_mut(asset) += amount;
+        self.total_fees_paid += EXCHANGE_FLAT_FEE;
+        Ok(cost)
+    }
+}
+
+/// Fraction of the price a trade worth `value` credits moves it: linear in
+/// size against the configured depth, capped so one order can't zero a market.
+fn trade_impact(value: f64, depth: f64) -> f64 {
+    if depth <= 0.0 {
+        return 0.0;
+    }
+    (value / depth).min(MAX_TRADE_IMPACT)
+}
+
+/// Completion nudge scales with payout, so big contracts move the market hardest.
+pub(super) fn market_impulse(impact: f64, payout_chain: f64) -> f64 {
+    (impact * payout_chain * 0.01).clamp(-5.0, 5.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sell_accepts_whole_balance_with_float_dust() {
+        let mut bank = BankState {
+            chain_balance: 0.3 - 1e-9,
+            ..BankState::default()
+        };
+        assert!(bank.sell(Asset::Chain, 0.3, 100.0).is_ok());
+        assert!(bank.chain_balance.abs() < 1e-6);
+    }
+
+    #[test]
+    fn sell_refuses_more_than_held() {
+        let mut bank = BankState {
+            chain_balance: 1.0,
+            ..BankState::default()
+        };
+        assert!(bank.sell(Asset::Chain, 1.1, 100.0).is_err());
+        assert_eq!(bank.chain_balance, 1.0);
+        assert_eq!(bank.credits_balance, 100.0);
+    }
+
+    #[test]
+    fn sell_pays_spread_and_flat_fee() {
+        let mut bank = BankState {
+            chain_balance: 2.0,
+            credits_balance: 0.0,
+            ..BankState::default()
+        };
+        let proceeds = bank.sell(Asset::Chain, 2.0, 50.0).unwrap();
+        let expected = 2.0 * 50.0 * (1.0 - DEFAULT_EXCHANGE_SPREAD) - EXCHANGE_FLAT_FEE;
+        assert_eq!(proceeds, expected);
+        assert_eq!(bank.credits_balance, expected);
+        assert_eq!(bank.total_fees_paid, EXCHANGE_FLAT_FEE);
+    }
+
+    #[test]
+    fn sell_refuses_when_fee_eats_proceeds() {
+        let mut bank = BankState {
+            shard_balance: 0.001,
+            ..BankState::default()
+        };
+        assert!(bank.sell(Asset::Shards, 0.001, 1.0).is_err());
+        assert_eq!(bank.shard_balance, 0.001);
+    }
+
+    #[test]
+    fn buy_charges_spread_and_fee_or_refuses() {
+        let mut bank = BankState::default();
+        let cost = bank.buy(Asset::Shards, 1.0, 10.0).unwrap();
+        assert_eq!(
+            cost,
+            10.0 * (1.0 + DEFAULT_EXCHANGE_SPREAD) + EXCHANGE_FLAT_FEE
+        );
+        assert_eq!(bank.shard_balance, 1.0);
+        assert!(bank.buy(Asset::Shards, 100.0, 10.0).is_err());
+        assert_eq!(bank.shard_balance, 1.0);
+    }
+
+    #[test]
+    fn savings_can_be_switched_off_without_pausing_loan_interest() {
+        let mut bank = BankState {
+            debt: 100.0,
+            ..BankState::default()
+        };
+        bank.accrue_interest(3_600.0, false);
+        assert_eq!(bank.credits_balance, 100.0);
+        assert_eq!(bank.interest_earned, 0.0);
+        assert!(bank.debt > 100.0);
+
+        bank.accrue_interest(3_600.0, true);
+        assert!(bank.credits_balance > 100.0);
+    }
+
+    #[test]
+    fn repay_is_capped_by_debt_and_credits() {
+        let mut bank = BankState {
+            credits_balance: 30.0,
+            debt: 50.0,
+            ..BankState::default()
+        };
+        assert_eq!(bank.repay(100.0), 30.0);
+        assert_eq!(bank.debt, 20.0);
+        bank.credits_balance = 100.0;
+        assert_eq!(bank.repay(100.0), 20.0);
+        assert_eq!(bank.debt, 0.0);
+    }
+}
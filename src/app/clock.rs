@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// Source of wall-clock timestamps for ledger entries. `on_tick` advances it
+/// by each tick's `dt`, so a simulated clock stays in step with game time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+
+    fn advance(&mut self, _dt: Duration) {}
+}
+
+/// The real clock; ticks don't move it.
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Time that only moves when ticked, for headless runs that must reproduce
+/// exactly from a seed.
+#[derive(Debug)]
+pub struct SimulatedClock {
+    now: DateTime<Utc>,
+}
+
+impl SimulatedClock {
+    pub fn starting_at(now: DateTime<Utc>) -> Self {
+        Self { now }
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    fn advance(&mut self, dt: Duration) {
+        self.now += dt;
+    }
+}
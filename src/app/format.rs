@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+/// Seconds to grind through `work` at `power`, or `None` while the rigs are idle.
+/// Leftover power carries across linklet boundaries, so only the total matters.
+pub fn estimate_secs(work: f64, power: f64) -> Option<f64> {
+    (power > 0.01).then(|| work.max(0.0) / power)
+}
+
+pub fn format_eta(secs: Option<f64>) -> String {
+    match secs {
+        Some(secs) => format_duration(Duration::from_secs_f64(secs)),
+        None => "—".to_string(),
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let millis = duration.subsec_millis();
+    if secs >= 3600 {
+        let hours = secs / 3600;
+        let minutes = (secs % 3600) / 60;
+        format!("{:02}h{:02}m", hours, minutes)
+    } else if secs >= 60 {
+        let minutes = secs / 60;
+        let seconds = secs % 60;
+        format!("{:02}m{:02}s", minutes, seconds)
+    } else {
+        format!("{:02}.{:03}s", secs, millis)
+    }
+}
+
+/// Decimal places that keep about three significant digits of `value` on
+/// screen: whole credits for big prices, down to four places near the floor.
+fn price_decimals(value: f64) -> usize {
+    match value.abs() {
+        v if v >= 1_000.0 => 0,
+        v if v >= 100.0 => 1,
+        v if v >= 1.0 => 2,
+        v if v >= 0.1 => 3,
+        _ => 4,
+    }
+}
+
+pub fn format_price(price: f64) -> String {
+    format!("{:.*}", price_decimals(price), price)
+}
+
+pub fn format_price_delta(delta: f64) -> String {
+    if delta.abs() < 0.00005 {
+        "±0.00".to_string()
+    } else if delta >= 0.0 {
+        format!("+{:.*}", price_decimals(delta), delta)
+    } else {
+        format!("{:.*}", price_decimals(delta), delta)
+    }
+}
+
+pub fn format_relings(power: f64) -> String {
+    const UNITS: [(&str, f64); 5] = [
+        ("Rl/s", 1.0),
+        ("kRl/s", 1_000.0),
+        ("MRl/s", 1_000_000.0),
+        ("GRl/s", 1_000_000_000.0),
+        ("TRl/s", 1_000_000_000_000.0),
+    ];
+    let mut value = power;
+    let mut idx = 0usize;
+    while value >= 1000.0 && idx + 1 < UNITS.len() {
+        value /= 1000.0;
+        idx += 1;
+    }
+    format!("{:.2} {}", value, UNITS[idx].0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_picks_unit_by_size() {
+        assert_eq!(format_duration(Duration::from_millis(4_250)), "04.250s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "02m05s");
+        assert_eq!(format_duration(Duration::from_secs(3_725)), "01h02m");
+    }
+
+    #[test]
+    fn price_keeps_about_three_significant_digits() {
+        assert_eq!(format_price(1_234.5), "1234");
+        assert_eq!(format_price(123.45), "123.5");
+        assert_eq!(format_price(12.345), "12.35");
+        assert_eq!(format_price(0.25), "0.250");
+        assert_eq!(format_price(0.01234), "0.0123");
+    }
+
+    #[test]
+    fn price_delta_is_signed() {
+        assert_eq!(format_price_delta(0.00001), "±0.00");
+        assert_eq!(format_price_delta(1.5), "+1.50");
+        assert_eq!(format_price_delta(-1.5), "-1.50");
+    }
+
+    #[test]
+    fn relings_scale_through_units() {
+        assert_eq!(format_relings(12.0), "12.00 Rl/s");
+        assert_eq!(format_relings(12_500.0), "12.50 kRl/s");
+        assert_eq!(format_relings(3_000_000.0), "3.00 MRl/s");
+    }
+
+    #[test]
+    fn idle_rigs_have_no_eta() {
+        assert_eq!(estimate_secs(10.0, 0.0), None);
+        assert_eq!(estimate_secs(10.0, 2.0), Some(5.0));
+        assert_eq!(format_eta(None), "—");
+    }
+}
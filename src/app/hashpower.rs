@@ -0,0 +1,563 @@
+use anyhow::{Result, anyhow};
+
+use super::bank::BankState;
+use crate::preset::Preset;
+
+pub(super) const OVERCLOCK_MULTIPLIER: f64 = 2.0;
+pub(super) const OVERCLOCK_DURATION_SECS: f64 = 30.0;
+const OVERCLOCK_COOLDOWN_SECS: f64 = 90.0;
+/// Credits burned per second for each unit of base hashpower while overclocked.
+const OVERCLOCK_DRAIN_PER_POWER: f64 = 0.5;
+/// Upper bound on units bought by one "buy max" press.
+const MAX_BULK_PURCHASE: u32 = 10_000;
+/// Units of a tier needed before the next tier can be bought.
+pub const TIER_UNLOCK_OWNED: u32 = 10;
+/// Index of the Foundry Core, the top tier the achievement asks for.
+pub(super) const FOUNDRY_CORE_TIER: usize = 9;
+
+#[derive(Debug, Clone)]
+pub struct HashpowerTier {
+    pub name: &'static str,
+    pub base_cost: f64,
+    pub cost_multiplier: f64,
+    pub power: f64,
+    pub owned: u32,
+    /// Output multiplier from purchased research; 1.0 until any is bought.
+    pub research_multiplier: f64,
+}
+
+impl HashpowerTier {
+    pub fn cost_for_next(&self) -> f64 {
+        let scaling = self.cost_multiplier.powi(self.owned as i32);
+        self.base_cost * scaling
+    }
+
+    pub fn total_power(&self) -> f64 {
+        self.power * self.owned as f64 * self.research_multiplier
+    }
+
+    pub fn unit_power(&self) -> f64 {
+        self.power * self.research_multiplier
+    }
+
+    /// Power the next unit adds per credit it costs.
+    pub fn power_per_credit(&self) -> f64 {
+        let cost = self.cost_for_next();
+        if cost <= f64::EPSILON {
+            0.0
+        } else {
+            self.unit_power() / cost
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResearchUpgrade {
+    pub name: &'static str,
+    pub tier: usize,
+    /// Additive bonus to the tier's output, e.g. 0.25 for +25%.
+    pub power_bonus: f64,
+    pub credit_cost: f64,
+    pub chain_cost: f64,
+    pub purchased: bool,
+}
+
+impl ResearchUpgrade {
+    const fn new(
+        name: &'static str,
+        tier: usize,
+        power_bonus: f64,
+        credit_cost: f64,
+        chain_cost: f64,
+    ) -> Self {
+        Self {
+            name,
+            tier,
+            power_bonus,
+            credit_cost,
+            chain_cost,
+            purchased: false,
+        }
+    }
+}
+
+fn research_catalog() -> Vec<ResearchUpgrade> {
+    vec![
+        ResearchUpgrade::new("Processor firmware v2", 0, 0.25, 250.0, 0.0),
+        ResearchUpgrade::new("Processor microcode v3", 0, 0.5, 1_500.0, 5.0),
+        ResearchUpgrade::new("Server RAID tuning", 1, 0.25, 1_400.0, 0.0),
+        ResearchUpgrade::new("Server liquid cooling", 1, 0.5, 6_000.0, 20.0),
+        ResearchUpgrade::new("Rack airflow redesign", 2, 0.25, 7_000.0, 0.0),
+        ResearchUpgrade::new("Rack fibre backplane", 2, 0.5, 30_000.0, 60.0),
+        ResearchUpgrade::new("Lab ASIC prototypes", 3, 0.25, 32_000.0, 0.0),
+        ResearchUpgrade::new("Lab clean-room fab", 3, 0.5, 120_000.0, 200.0),
+        ResearchUpgrade::new("Supercomputer vector units", 4, 0.25, 130_000.0, 100.0),
+        ResearchUpgrade::new(
+            "Datacenter hot-aisle containment",
+            5,
+            0.25,
+            500_000.0,
+            300.0,
+        ),
+        ResearchUpgrade::new("Quantum error correction", 6, 0.25, 2_100_000.0, 900.0),
+        ResearchUpgrade::new("Orbital solar sails", 7, 0.25, 8_000_000.0, 2_500.0),
+        ResearchUpgrade::new("Darknet mesh routing", 8, 0.25, 30_000_000.0, 8_000.0),
+        ResearchUpgrade::new("Foundry core annealing", 9, 0.25, 95_000_000.0, 25_000.0),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashpowerView {
+    #[default]
+    Tiers,
+    Research,
+}
+
+#[derive(Debug)]
+pub struct HashpowerState {
+    pub tiers: Vec<HashpowerTier>,
+    pub selected: usize,
+    pub overclock: Overclock,
+    pub research: Vec<ResearchUpgrade>,
+    pub research_selected: usize,
+    pub view: HashpowerView,
+}
+
+impl Default for HashpowerState {
+    fn default() -> Self {
+        Self {
+            tiers: vec![
+                HashpowerTier {
+                    name: "Processor",
+                    base_cost: 75.0,
+                    cost_multiplier: 1.18,
+                    power: 1.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Server",
+                    base_cost: 420.0,
+                    cost_multiplier: 1.20,
+                    power: 4.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Rack",
+                    base_cost: 2100.0,
+                    cost_multiplier: 1.22,
+                    power: 18.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Lab",
+                    base_cost: 9500.0,
+                    cost_multiplier: 1.24,
+                    power: 65.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Supercomputer",
+                    base_cost: 38000.0,
+                    cost_multiplier: 1.26,
+                    power: 220.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Datacenter",
+                    base_cost: 150000.0,
+                    cost_multiplier: 1.28,
+                    power: 800.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Quantum Array",
+                    base_cost: 620000.0,
+                    cost_multiplier: 1.31,
+                    power: 3000.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Orbital Node",
+                    base_cost: 2_400_000.0,
+                    cost_multiplier: 1.34,
+                    power: 10_500.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Darknet Farm",
+                    base_cost: 8_600_000.0,
+                    cost_multiplier: 1.38,
+                    power: 34_000.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+                HashpowerTier {
+                    name: "Foundry Core",
+                    base_cost: 28_000_000.0,
+                    cost_multiplier: 1.42,
+                    power: 120_000.0,
+                    owned: 0,
+                    research_multiplier: 1.0,
+                },
+            ],
+            selected: 0,
+            overclock: Overclock::default(),
+            research: research_catalog(),
+            research_selected: 0,
+            view: HashpowerView::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Overclock {
+    #[default]
+    Idle,
+    Active {
+        remaining_secs: f64,
+    },
+    Cooldown {
+        remaining_secs: f64,
+    },
+}
+
+impl HashpowerState {
+    pub(super) fn starting(preset: Preset) -> Self {
+        let mut state = Self::default();
+        if let Some((tier, count)) = preset.starting_rig() {
+            state.tiers[tier].owned = count;
+        }
+        state
+    }
+
+    pub fn total_power(&self) -> f64 {
+        self.tiers.iter().map(|tier| tier.total_power()).sum()
+    }
+
+    /// Fraction of base power the tier at `index` supplies; zero with no rigs.
+    pub fn power_share(&self, index: usize) -> f64 {
+        let total = self.total_power();
+        match self.tiers.get(index) {
+            Some(tier) if total > 0.0 => tier.total_power() / total,
+            _ => 0.0,
+        }
+    }
+
+    /// A tier opens once `TIER_UNLOCK_OWNED` of the one before it are owned.
+    /// Tiers already owned stay open, so undoing a purchase never strands them.
+    pub fn is_unlocked(&self, index: usize) -> bool {
+        match index.checked_sub(1) {
+            None => true,
+            Some(previous) => {
+                self.tiers.get(index).is_some_and(|tier| tier.owned > 0)
+                    || self
+                        .tiers
+                        .get(previous)
+                        .is_some_and(|tier| tier.owned >= TIER_UNLOCK_OWNED)
+            }
+        }
+    }
+
+    /// Total power with any running overclock applied.
+    pub fn effective_power(&self) -> f64 {
+        match self.overclock {
+            Overclock::Active { .. } => self.total_power() * OVERCLOCK_MULTIPLIER,
+            _ => self.total_power(),
+        }
+    }
+
+    pub(super) fn overclock_drain_per_sec(&self) -> f64 {
+        self.total_power() * OVERCLOCK_DRAIN_PER_POWER
+    }
+
+    pub(super) fn engage_overclock(&mut self) -> Result<()> {
+        match self.overclock {
+            Overclock::Active { .. } => Err(anyhow!("already overclocked")),
+            Overclock::Cooldown { remaining_secs } => {
+                Err(anyhow!("rigs cooling down for {:.0}s", remaining_secs))
+            }
+            Overclock::Idle => {
+                self.overclock = Overclock::Active {
+                    remaining_secs: OVERCLOCK_DURATION_SECS,
+                };
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs the overclock timer and burns credits while it is active. Returns a
+    /// message when the burst ends, either on schedule or because credits ran dry.
+    pub(super) fn advance_overclock(&mut self, secs: f64, bank: &mut BankState) -> Option<String> {
+        match self.overclock {
+            Overclock::Idle => None,
+            Overclock::Cooldown { remaining_secs } => {
+                let remaining_secs = remaining_secs - secs;
+                if remaining_secs <= 0.0 {
+                    self.overclock = Overclock::Idle;
+                    Some("Rigs cooled down; overclock ready".to_string())
+                } else {
+                    self.overclock = Overclock::Cooldown { remaining_secs };
+                    None
+                }
+            }
+            Overclock::Active { remaining_secs } => {
+                let drain = self.overclock_drain_per_sec() * secs;
+                let cooldown = Overclock::Cooldown {
+                    remaining_secs: OVERCLOCK_COOLDOWN_SECS,
+                };
+                if bank.credits_balance < drain {
+                    bank.credits_balance = 0.0;
+                    self.overclock = cooldown;
+                    return Some("Overclock cut out: out of credits".to_string());
+                }
+                bank.credits_balance -= drain;
+                let remaining_secs = remaining_secs - secs;
+                if remaining_secs <= 0.0 {
+                    self.overclock = cooldown;
+                    Some("Overclock ended; rigs cooling down".to_string())
+                } else {
+                    self.overclock = Overclock::Active { remaining_secs };
+                    None
+                }
+            }
+        }
+    }
+
+    pub(super) fn owned_counts(&self) -> Vec<u32> {
+        self.tiers.iter().map(|tier| tier.owned).collect()
+    }
+
+    pub(super) fn apply_owned(&mut self, owned: &[u32], selected: usize) {
+        for (tier, &amount) in self.tiers.iter_mut().zip(owned.iter()) {
+            tier.owned = amount;
+        }
+        if owned.len() < self.tiers.len() {
+            for tier in self.tiers[owned.len()..].iter_mut() {
+                tier.owned = 0;
+            }
+        }
+        if self.tiers.is_empty() {
+            self.selected = 0;
+        } else {
+            self.selected = selected.min(self.tiers.len() - 1);
+        }
+    }
+
+    pub(super) fn select_next(&mut self) {
+        match self.view {
+            HashpowerView::Tiers => self.selected = (self.selected + 1) % self.tiers.len(),
+            HashpowerView::Research => {
+                self.research_selected = (self.research_selected + 1) % self.research.len();
+            }
+        }
+    }
+
+    pub(super) fn select_previous(&mut self) {
+        let (selected, len) = match self.view {
+            HashpowerView::Tiers => (&mut self.selected, self.tiers.len()),
+            HashpowerView::Research => (&mut self.research_selected, self.research.len()),
+        };
+        if *selected == 0 {
+            *selected = len - 1;
+        } else {
+            *selected -= 1;
+        }
+    }
+
+    pub(super) fn toggle_view(&mut self) {
+        self.view = match self.view {
+            HashpowerView::Tiers => HashpowerView::Research,
+            HashpowerView::Research => HashpowerView::Tiers,
+        };
+    }
+
+    pub(super) fn purchase_research(&mut self, index: usize, bank: &mut BankState) -> Result<()> {
+        let upgrade = self
+            .research
+            .get(index)
+            .ok_or_else(|| anyhow!("no such research"))?;
+        if upgrade.purchased {
+            return Err(anyhow!("{} already researched", upgrade.name));
+        }
+        if bank.credits_balance + 1e-6 < upgrade.credit_cost {
+            return Err(anyhow!(
+                "{} needs {:.2}₵",
+                upgrade.name,
+                upgrade.credit_cost
+            ));
+        }
+        if bank.chain_balance + 1e-6 < upgrade.chain_cost {
+            return Err(anyhow!(
+                "{} needs {:.2} ⛓",
+                upgrade.name,
+                upgrade.chain_cost
+            ));
+        }
+        bank.credits_balance -= upgrade.credit_cost;
+        bank.chain_balance -= upgrade.chain_cost;
+        self.research[index].purchased = true;
+        self.refresh_research_multipliers();
+        Ok(())
+    }
+
+    fn refresh_research_multipliers(&mut self) {
+        for tier in self.tiers.iter_mut() {
+            tier.research_multiplier = 1.0;
+        }
+        for upgrade in self.research.iter().filter(|upgrade| upgrade.purchased) {
+            if let Some(tier) = self.tiers.get_mut(upgrade.tier) {
+                tier.research_multiplier += upgrade.power_bonus;
+            }
+        }
+    }
+
+    pub(super) fn purchased_research(&self) -> Vec<String> {
+        self.research
+            .iter()
+            .filter(|upgrade| upgrade.purchased)
+            .map(|upgrade| upgrade.name.to_string())
+            .collect()
+    }
+
+    pub(super) fn apply_research(&mut self, purchased: &[String]) {
+        for upgrade in self.research.iter_mut() {
+            upgrade.purchased = purchased.iter().any(|name| name == upgrade.name);
+        }
+        self.refresh_research_multipliers();
+    }
+
+    /// The tier whose next unit buys the most power per credit.
+    pub fn most_efficient_tier(&self) -> Option<usize> {
+        self.tiers
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| self.is_unlocked(idx))
+            .max_by(|(_, a), (_, b)| a.power_per_credit().total_cmp(&b.power_per_credit()))
+            .map(|(idx, _)| idx)
+    }
+
+    /// How many consecutive units of a tier `credits` would buy, stopping at
+    /// `limit` when given, and what they would cost in total.
+    pub fn quote_tier_bulk(
+        &self,
+        index: usize,
+        limit: Option<u32>,
+        credits: f64,
+    ) -> Option<(u32, f64)> {
+        if !self.is_unlocked(index) {
+            return None;
+        }
+        let mut probe = self.tiers.get(index)?.clone();
+        let limit = limit.unwrap_or(MAX_BULK_PURCHASE);
+        let mut count = 0;
+        let mut total = 0.0;
+        while count < limit {
+            let cost = probe.cost_for_next();
+            // A free tier would otherwise loop until the cap on every press.
+            if cost <= 0.0 || total + cost > credits {
+                break;
+            }
+            total += cost;
+            count += 1;
+            probe.owned += 1;
+        }
+        (count > 0).then_some((count, total))
+    }
+
+    /// Buys consecutive units of a tier while credits last, stopping at `limit`
+    /// when given. Returns the number bought and the total spent.
+    pub fn purchase_tier_bulk(
+        &mut self,
+        index: usize,
+        limit: Option<u32>,
+        bank: &mut BankState,
+    ) -> Option<(u32, f64)> {
+        let (count, total) = self.quote_tier_bulk(index, limit, bank.credits_balance)?;
+        bank.credits_balance -= total;
+        self.tiers[index].owned += count;
+        Some((count, total))
+    }
+
+    pub fn purchase_tier(&mut self, index: usize, bank: &mut BankState) -> Option<f64> {
+        if !self.is_unlocked(index) {
+            return None;
+        }
+        let tier = self.tiers.get_mut(index)?;
+        let cost = tier.cost_for_next();
+        if bank.credits_balance >= cost {
+            bank.credits_balance -= cost;
+            tier.owned += 1;
+            Some(cost)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foundry_core_index_points_at_the_top_tier() {
+        let tiers = HashpowerState::default().tiers;
+        assert_eq!(tiers[FOUNDRY_CORE_TIER].name, "Foundry Core");
+        assert_eq!(FOUNDRY_CORE_TIER, tiers.len() - 1);
+    }
+
+    #[test]
+    fn each_unit_costs_more_than_the_last() {
+        let mut tier = HashpowerState::default().tiers[0].clone();
+        let first = tier.cost_for_next();
+        tier.owned = 3;
+        assert_eq!(tier.cost_for_next(), first * tier.cost_multiplier.powi(3));
+    }
+
+    #[test]
+    fn bulk_purchase_spends_exactly_the_summed_costs() {
+        let mut hashpower = HashpowerState::default();
+        let mut bank = BankState {
+            credits_balance: 1_000.0,
+            ..BankState::default()
+        };
+        let mut probe = hashpower.tiers[0].clone();
+        let mut expected = 0.0;
+        for _ in 0..5 {
+            expected += probe.cost_for_next();
+            probe.owned += 1;
+        }
+        let (count, spent) = hashpower.purchase_tier_bulk(0, Some(5), &mut bank).unwrap();
+        assert_eq!(count, 5);
+        assert_eq!(spent, expected);
+        assert_eq!(bank.credits_balance, 1_000.0 - expected);
+        assert_eq!(hashpower.tiers[0].owned, 5);
+    }
+
+    #[test]
+    fn bulk_purchase_stops_when_credits_run_out() {
+        let mut hashpower = HashpowerState::default();
+        let mut bank = BankState {
+            credits_balance: 1.0,
+            ..BankState::default()
+        };
+        assert!(hashpower.purchase_tier_bulk(0, None, &mut bank).is_none());
+        assert_eq!(bank.credits_balance, 1.0);
+    }
+
+    #[test]
+    fn next_tier_unlocks_at_threshold() {
+        let mut hashpower = HashpowerState::default();
+        assert!(hashpower.is_unlocked(0));
+        assert!(!hashpower.is_unlocked(1));
+        hashpower.tiers[0].owned = TIER_UNLOCK_OWNED;
+        assert!(hashpower.is_unlocked(1));
+    }
+}
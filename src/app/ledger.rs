@@ -0,0 +1,195 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+
+use super::bank::Asset;
+
+#[derive(Debug, Default)]
+pub struct LedgerState {
+    pub entries: Vec<LedgerEntry>,
+    pub scroll: usize,
+    /// Case-insensitive substring matched against entry names and ids.
+    pub filter: String,
+    pub filter_editing: bool,
+}
+
+impl LedgerState {
+    pub(super) fn add_entry(&mut self, entry: LedgerEntry) {
+        self.entries.insert(0, entry);
+    }
+
+    /// Entries matching the active filter, newest first. `scroll` indexes into this list.
+    pub fn visible_entries(&self) -> Vec<&LedgerEntry> {
+        if self.filter.is_empty() {
+            return self.entries.iter().collect();
+        }
+        let query = self.filter.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.name.to_lowercase().contains(&query)
+                    || entry.id.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    pub(super) fn scroll_up(&mut self) {
+        if self.scroll > 0 {
+            self.scroll -= 1;
+        }
+    }
+
+    pub(super) fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.visible_entries().len() {
+            self.scroll += 1;
+        }
+    }
+
+    /// Moves up by `page` rows, stopping at the newest entry.
+    pub(super) fn page_up(&mut self, page: usize) {
+        self.scroll = self.scroll.saturating_sub(page.max(1));
+    }
+
+    /// Moves down by `page` rows, stopping at the oldest entry.
+    pub(super) fn page_down(&mut self, page: usize) {
+        let last = self.visible_entries().len().saturating_sub(1);
+        self.scroll = (self.scroll + page.max(1)).min(last);
+    }
+
+    pub(super) fn jump_newest(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub(super) fn jump_oldest(&mut self) {
+        self.scroll = self.visible_entries().len().saturating_sub(1);
+    }
+
+    /// Totals over the entries matching the active filter.
+    pub fn summary(&self) -> LedgerSummary {
+        let entries = self.visible_entries();
+        let mut summary = LedgerSummary {
+            entries: entries.len(),
+            ..LedgerSummary::default()
+        };
+        for entry in &entries {
+            match entry.asset {
+                Asset::Chain => summary.total_payout += entry.payout_chain,
+                Asset::Shards => summary.total_shards += entry.payout_chain,
+            }
+            summary.total_credits += entry.credits_at_completion;
+            summary.total_market_impact += entry.market_impact;
+            summary.average_difficulty += entry.difficulty;
+        }
+        if summary.entries > 0 {
+            summary.average_difficulty /= summary.entries as f64;
+        }
+        summary
+    }
+
+    pub(super) fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.scroll = 0;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LedgerSummary {
+    pub entries: usize,
+    pub total_payout: f64,
+    pub total_shards: f64,
+    pub total_credits: f64,
+    pub average_difficulty: f64,
+    pub total_market_impact: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LedgerEntry {
+    pub id: String,
+    pub asset: Asset,
+    pub name: String,
+    pub finished_at: DateTime<Utc>,
+    pub difficulty: f64,
+    pub payout_chain: f64,
+    pub credits_at_completion: f64,
+    pub duration: Duration,
+    pub market_impact: f64,
+}
+
+impl LedgerEntry {
+    pub fn finished_local(&self) -> DateTime<Local> {
+        self.finished_at.with_timezone(&Local)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, name: &str) -> LedgerEntry {
+        LedgerEntry {
+            id: id.to_string(),
+            asset: Asset::Chain,
+            name: name.to_string(),
+            finished_at: DateTime::UNIX_EPOCH,
+            difficulty: 10.0,
+            payout_chain: 1.0,
+            credits_at_completion: 30.0,
+            duration: Duration::from_secs(5),
+            market_impact: 0.0,
+        }
+    }
+
+    fn ledger(count: usize) -> LedgerState {
+        let mut ledger = LedgerState::default();
+        for idx in 0..count {
+            ledger.add_entry(entry(&format!("LNK-{:04}", idx), "Ghost Relay"));
+        }
+        ledger
+    }
+
+    #[test]
+    fn filter_matches_name_or_id_ignoring_case() {
+        let mut ledger = ledger(0);
+        ledger.add_entry(entry("LNK-AAAA", "Ghost Relay"));
+        ledger.add_entry(entry("LNK-BBBB", "Null Vector"));
+        ledger.scroll = 1;
+        ledger.set_filter("ghost".to_string());
+        assert_eq!(ledger.scroll, 0);
+        assert_eq!(ledger.visible_entries().len(), 1);
+        ledger.set_filter("bbbb".to_string());
+        assert_eq!(ledger.visible_entries()[0].name, "Null Vector");
+    }
+
+    #[test]
+    fn paging_clamps_at_both_ends() {
+        let mut ledger = ledger(25);
+        ledger.page_down(10);
+        assert_eq!(ledger.scroll, 10);
+        ledger.page_down(10);
+        ledger.page_down(10);
+        assert_eq!(ledger.scroll, 24);
+        ledger.page_up(10);
+        assert_eq!(ledger.scroll, 14);
+        ledger.page_up(100);
+        assert_eq!(ledger.scroll, 0);
+        ledger.jump_oldest();
+        assert_eq!(ledger.scroll, 24);
+        ledger.jump_newest();
+        assert_eq!(ledger.scroll, 0);
+    }
+
+    #[test]
+    fn summary_splits_chain_and_shard_payouts() {
+        let mut ledger = ledger(2);
+        ledger.add_entry(LedgerEntry {
+            asset: Asset::Shards,
+            payout_chain: 4.0,
+            ..entry("LNK-SHRD", "Hollow Shard")
+        });
+        let summary = ledger.summary();
+        assert_eq!(summary.entries, 3);
+        assert_eq!(summary.total_payout, 2.0);
+        assert_eq!(summary.total_shards, 4.0);
+        assert_eq!(summary.average_difficulty, 10.0);
+    }
+}
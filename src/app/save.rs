@@ -0,0 +1,451 @@
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::bank::{Asset, BankState};
+use super::ledger::LedgerEntry;
+use super::mining::{ActiveJob, ContractSort, LinkletProgress, MiningJob, WorkStrategy};
+use super::ticker::{
+    ActiveEvent, ChartMode, ChartScale, PriceAlert, SHARD_INITIAL_PRICE, TickerState,
+};
+use super::{
+    App, DEFAULT_AUTOSAVE_SECS, FeedMessage, GridGlyphs, MAX_MESSAGES, PaneFocus, SessionStats,
+};
+use crate::achievements::AchievementId;
+use crate::goal::Goal;
+use crate::preset::Preset;
+use crate::theme::ThemeKind;
+
+pub(super) const SAVE_FILE: &str = "blockgrave-save.json";
+/// The save that `SAVE_FILE` replaced, kept in case the new one is unreadable.
+pub(super) const SAVE_BACKUP_FILE: &str = "blockgrave-save.json.bak";
+const SAVE_TEMP_FILE: &str = "blockgrave-save.json.tmp";
+pub(super) const EXPORT_FILE: &str = "blockgrave-export.txt";
+pub(super) const EXPORT_PREFIX: &str = "blockgrave";
+const EXPORT_VERSION: u32 = 1;
+/// Bumped whenever `SaveData` changes shape; files without a version are treated as 0.
+const SAVE_VERSION: u32 = 4;
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct SaveData {
+    #[serde(default)]
+    version: u32,
+    focus: PaneFocus,
+    #[serde(default)]
+    seed: Option<u64>,
+    mining: MiningSave,
+    hashpower_owned: Vec<u32>,
+    hashpower_selected: usize,
+    #[serde(default)]
+    research_purchased: Vec<String>,
+    bank: BankState,
+    ledger: Vec<LedgerEntrySave>,
+    ledger_scroll: usize,
+    ticker: TickerSave,
+    #[serde(default)]
+    shard_ticker: Option<TickerSave>,
+    #[serde(default)]
+    bank_asset: Asset,
+    messages: Vec<FeedMessage>,
+    #[serde(default)]
+    stats: SessionStats,
+    #[serde(default = "default_legacy_multiplier")]
+    legacy_multiplier: f64,
+    #[serde(default = "default_autosave_secs")]
+    autosave_interval_secs: u64,
+    #[serde(default)]
+    theme: ThemeKind,
+    #[serde(default)]
+    grid_glyphs: GridGlyphs,
+    #[serde(default = "default_pause_on_focus_loss")]
+    pause_on_focus_loss: bool,
+    #[serde(default)]
+    achievements: HashSet<AchievementId>,
+    #[serde(default)]
+    goal: Option<Goal>,
+    #[serde(default)]
+    preset: Preset,
+}
+
+pub(super) fn encode_export(snapshot: &SaveData) -> Result<String> {
+    let json = serde_json::to_vec(snapshot)?;
+    Ok(format!(
+        "{}:{}:{}",
+        EXPORT_PREFIX,
+        EXPORT_VERSION,
+        BASE64.encode(json)
+    ))
+}
+
+pub(super) fn decode_export(code: &str) -> Result<SaveData> {
+    let mut parts = code.trim().splitn(3, ':');
+    let (Some(EXPORT_PREFIX), Some(version), Some(body)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!("not a Blockgrave export code"));
+    };
+    let version: u32 = version
+        .parse()
+        .with_context(|| format!("invalid export version '{}'", version))?;
+    if version != EXPORT_VERSION {
+        return Err(anyhow!(
+            "export code v{} is not supported (this build reads v{})",
+            version,
+            EXPORT_VERSION
+        ));
+    }
+    let json = BASE64
+        .decode(body.trim())
+        .context("export code is corrupt (bad base64)")?;
+    parse_save(&json).context("export code is corrupt or incompatible")
+}
+
+/// Which file `load_game` restored from; a backup load carries the main file's error.
+pub(super) enum LoadSource {
+    Save,
+    Backup(anyhow::Error),
+}
+
+pub(super) fn read_save_file(path: &str) -> Result<SaveData> {
+    if !Path::new(path).exists() {
+        return Err(anyhow!("no save data available"));
+    }
+    let data = fs::read(path).with_context(|| format!("reading {}", path))?;
+    parse_save(&data).with_context(|| format!("reading stored game state from {}", path))
+}
+
+/// Writes to a temp file and renames it over the save, so a crash mid-write
+/// leaves either the old save or the new one. The old one is copied to the
+/// backup first, keeping a main save in place throughout.
+pub(super) fn write_save_atomically(payload: &[u8]) -> Result<()> {
+    let mut file =
+        File::create(SAVE_TEMP_FILE).with_context(|| format!("creating {}", SAVE_TEMP_FILE))?;
+    file.write_all(payload)
+        .with_context(|| format!("writing {}", SAVE_TEMP_FILE))?;
+    file.sync_all()
+        .with_context(|| format!("syncing {}", SAVE_TEMP_FILE))?;
+    drop(file);
+    if Path::new(SAVE_FILE).exists() {
+        fs::copy(SAVE_FILE, SAVE_BACKUP_FILE)
+            .with_context(|| format!("backing up {}", SAVE_FILE))?;
+    }
+    fs::rename(SAVE_TEMP_FILE, SAVE_FILE).with_context(|| format!("replacing {}", SAVE_FILE))
+}
+
+/// Parses a serialized `SaveData`, upgrading older versions in place first.
+fn parse_save(data: &[u8]) -> Result<SaveData> {
+    let mut value: serde_json::Value = serde_json::from_slice(data).context("malformed JSON")?;
+    let version = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0) as u32;
+    if version > SAVE_VERSION {
+        return Err(anyhow!(
+            "save created by a newer version (v{}; this build reads up to v{})",
+            version,
+            SAVE_VERSION
+        ));
+    }
+    migrate_save(&mut value, version)?;
+    serde_json::from_value(value).with_context(|| format!("decoding v{} save", version))
+}
+
+/// Steps a save forward one version at a time until it matches `SAVE_VERSION`.
+fn migrate_save(value: &mut serde_json::Value, from: u32) -> Result<()> {
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("save is not a JSON object"))?;
+    for version in from..SAVE_VERSION {
+        match version {
+            // v0 predates versioning; every field added since has a serde default.
+            0 => {}
+            // v2 added shards; a missing shard ticker is seeded fresh on apply.
+            1 => {}
+            // v3 tags feed messages with a category and repeat count.
+            2 => {
+                if let Some(serde_json::Value::Array(messages)) = object.get_mut("messages") {
+                    for message in messages.iter_mut() {
+                        if let serde_json::Value::String(text) = message {
+                            *message = serde_json::json!({ "text": text });
+                        }
+                    }
+                }
+            }
+            // v4 added staking, auto-sell, the spread, goals, the preset and
+            // achievements, all defaulted when missing. The bump exists so v3
+            // builds refuse these saves rather than silently dropping a stake.
+            3 => {}
+            _ => unreachable!("no migration from v{}", version),
+        }
+        object.insert("version".to_string(), (version + 1).into());
+    }
+    Ok(())
+}
+
+fn default_autosave_secs() -> u64 {
+    DEFAULT_AUTOSAVE_SECS
+}
+
+fn default_legacy_multiplier() -> f64 {
+    1.0
+}
+
+fn default_pause_on_focus_loss() -> bool {
+    true
+}
+
+impl SaveData {
+    pub(super) fn from_app(app: &App) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            focus: app.focus,
+            seed: app.seed,
+            mining: app.mining.to_save(),
+            hashpower_owned: app.hashpower.owned_counts(),
+            hashpower_selected: app.hashpower.selected,
+            research_purchased: app.hashpower.purchased_research(),
+            bank: app.bank.clone(),
+            ledger: app
+                .ledger
+                .entries
+                .iter()
+                .map(LedgerEntrySave::from_entry)
+                .collect(),
+            ledger_scroll: app.ledger.scroll,
+            ticker: app.ticker.to_save(),
+            shard_ticker: Some(app.shard_ticker.to_save()),
+            bank_asset: app.bank_asset,
+            messages: app.messages.iter().cloned().collect(),
+            stats: app.stats.clone(),
+            legacy_multiplier: app.legacy_multiplier,
+            autosave_interval_secs: app.autosave_interval.as_secs(),
+            theme: app.theme,
+            grid_glyphs: app.grid_glyphs,
+            pause_on_focus_loss: app.pause_on_focus_loss,
+            achievements: app.achievements.clone(),
+            goal: app.goal.clone(),
+            preset: app.preset,
+        }
+    }
+
+    pub(super) fn apply(self, app: &mut App) -> Result<()> {
+        app.focus = self.focus;
+        app.seed = self.seed;
+        app.mining.apply_save(self.mining);
+        app.hashpower
+            .apply_owned(&self.hashpower_owned, self.hashpower_selected);
+        app.hashpower.apply_research(&self.research_purchased);
+        app.bank = self.bank;
+        app.ledger.entries = self
+            .ledger
+            .into_iter()
+            .map(LedgerEntrySave::into_entry)
+            .collect::<Result<Vec<_>>>()?;
+        app.ledger.filter.clear();
+        app.ledger.filter_editing = false;
+        if app.ledger.entries.is_empty() {
+            app.ledger.scroll = 0;
+        } else {
+            app.ledger.scroll = self.ledger_scroll.min(app.ledger.entries.len() - 1);
+        }
+        let history_cap = app.config.price_history;
+        app.ticker = TickerState::from_save(self.ticker, history_cap);
+        app.shard_ticker = match self.shard_ticker {
+            Some(save) => TickerState::from_save(save, history_cap),
+            None => TickerState::new(SHARD_INITIAL_PRICE, history_cap, &mut app.rng),
+        };
+        app.bank_asset = self.bank_asset;
+        app.mining.quotes = app.quotes();
+        app.messages = VecDeque::from(self.messages);
+        while app.messages.len() > MAX_MESSAGES {
+            app.messages.pop_back();
+        }
+        app.stats = self.stats;
+        app.legacy_multiplier = self.legacy_multiplier.max(1.0);
+        app.autosave_interval = Duration::from_secs(self.autosave_interval_secs);
+        app.theme = self.theme;
+        app.grid_glyphs = self.grid_glyphs;
+        app.pause_on_focus_loss = self.pause_on_focus_loss;
+        app.achievements = self.achievements;
+        app.goal = self.goal;
+        app.preset = self.preset;
+        app.mining.payout_scale = self.preset.payout_multiplier();
+        app.goal_draft = None;
+        app.autosave_elapsed = Duration::ZERO;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct MiningSave {
+    pub(super) available_jobs: Vec<MiningJob>,
+    pub(super) selected_job: usize,
+    pub(super) active_job: Option<ActiveJobSave>,
+    #[serde(default)]
+    pub(super) auto_accept: bool,
+    #[serde(default)]
+    pub(super) sort: ContractSort,
+    #[serde(default)]
+    pub(super) strategy: WorkStrategy,
+    #[serde(default)]
+    pub(super) refresh_timers: Vec<f64>,
+    #[serde(default)]
+    pub(super) reroll_cooldown: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct ActiveJobSave {
+    pub(super) job: MiningJob,
+    pub(super) linklets: Vec<LinkletProgress>,
+    pub(super) current_index: usize,
+    pub(super) elapsed_secs: f64,
+}
+
+impl ActiveJobSave {
+    pub(super) fn from_active(active: &ActiveJob) -> Self {
+        Self {
+            job: active.job.clone(),
+            linklets: active.linklets.clone(),
+            current_index: active.current_index,
+            elapsed_secs: active.work_time.as_secs_f64(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LedgerEntrySave {
+    pub(super) id: String,
+    #[serde(default)]
+    pub(super) asset: Asset,
+    pub(super) name: String,
+    pub(super) finished_at_ms: i64,
+    pub(super) difficulty: f64,
+    pub(super) payout_chain: f64,
+    pub(super) credits_at_completion: f64,
+    pub(super) duration_secs: f64,
+    pub(super) market_impact: f64,
+}
+
+impl LedgerEntrySave {
+    fn from_entry(entry: &LedgerEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            asset: entry.asset,
+            name: entry.name.clone(),
+            finished_at_ms: entry.finished_at.timestamp_millis(),
+            difficulty: entry.difficulty,
+            payout_chain: entry.payout_chain,
+            credits_at_completion: entry.credits_at_completion,
+            duration_secs: entry.duration.as_secs_f64(),
+            market_impact: entry.market_impact,
+        }
+    }
+
+    fn into_entry(self) -> Result<LedgerEntry> {
+        let finished_at = DateTime::<Utc>::from_timestamp_millis(self.finished_at_ms)
+            .ok_or_else(|| anyhow!("invalid timestamp in save data"))?;
+        Ok(LedgerEntry {
+            id: self.id,
+            asset: self.asset,
+            name: self.name,
+            finished_at,
+            difficulty: self.difficulty,
+            payout_chain: self.payout_chain,
+            credits_at_completion: self.credits_at_completion,
+            duration: Duration::from_secs_f64(self.duration_secs.max(0.0)),
+            market_impact: self.market_impact,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct TickerSave {
+    pub(super) price: f64,
+    pub(super) last_delta: f64,
+    pub(super) history: Vec<f64>,
+    #[serde(default)]
+    pub(super) alerts: Vec<PriceAlert>,
+    #[serde(default)]
+    pub(super) events: Vec<ActiveEvent>,
+    #[serde(default)]
+    pub(super) chart: ChartMode,
+    #[serde(default)]
+    pub(super) chart_scale: ChartScale,
+    pub(super) time_since_update_secs: f64,
+    pub(super) update_interval_secs: f64,
+    /// Keeps candle buckets aligned across a load.
+    #[serde(default)]
+    pub(super) trimmed_samples: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::test_app;
+
+    #[test]
+    fn export_code_round_trips() {
+        let mut app = test_app(11, Preset::Hardcore);
+        app.bank.chain_balance = 42.5;
+        let code = encode_export(&SaveData::from_app(&app)).unwrap();
+        let mut restored = test_app(99, Preset::Hardcore);
+        decode_export(&code).unwrap().apply(&mut restored).unwrap();
+        assert_eq!(restored.seed, Some(11));
+        assert_eq!(restored.preset, Preset::Hardcore);
+        assert_eq!(restored.bank.chain_balance, 42.5);
+        assert_eq!(
+            restored.mining.available_jobs.len(),
+            app.mining.available_jobs.len()
+        );
+    }
+
+    #[test]
+    fn export_code_rejects_foreign_or_future_codes() {
+        assert!(decode_export("not-a-code").is_err());
+        assert!(decode_export("blockgrave:99:e30=").is_err());
+        assert!(decode_export("blockgrave:1:!!!").is_err());
+    }
+
+    #[test]
+    fn newer_save_versions_are_refused() {
+        let data = serde_json::json!({ "version": SAVE_VERSION + 1 }).to_string();
+        let err = parse_save(data.as_bytes()).err().unwrap();
+        assert!(format!("{:#}", err).contains("newer version"));
+    }
+
+    #[test]
+    fn v2_plain_messages_gain_categories() {
+        let mut value =
+            serde_json::to_value(SaveData::from_app(&test_app(5, Preset::Hardcore))).unwrap();
+        value["version"] = 2.into();
+        value["messages"] = serde_json::json!(["hello", "world"]);
+        let data = serde_json::to_vec(&value).unwrap();
+        let save = parse_save(&data).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.messages.len(), 2);
+        assert_eq!(save.messages[0].text, "hello");
+        assert_eq!(save.messages[0].count, 1);
+    }
+
+    #[test]
+    fn v3_saves_load_with_staking_defaults() {
+        let mut value =
+            serde_json::to_value(SaveData::from_app(&test_app(6, Preset::Hardcore))).unwrap();
+        value["version"] = 3.into();
+        let bank = value["bank"].as_object_mut().unwrap();
+        bank.remove("staked");
+        bank.remove("stake_lock");
+        let data = serde_json::to_vec(&value).unwrap();
+        let save = parse_save(&data).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.bank.staked, 0.0);
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use super::bank::market_impulse;
+use super::save::TickerSave;
+
+const PRICE_UPDATE_MIN_SECS: f64 = 5.0;
+const PRICE_UPDATE_MAX_SECS: f64 = 15.0;
+pub const SHORT_MA_WINDOW: usize = 10;
+pub const LONG_MA_WINDOW: usize = 30;
+const MARKET_EVENT_CHANCE_PER_SEC: f64 = 1.0 / 900.0;
+pub(super) const SHARD_INITIAL_PRICE: f64 = 9.0;
+/// Lowest price any market can be pushed to.
+pub(super) const PRICE_FLOOR: f64 = 0.25;
+/// Price samples each ticker keeps for the chart and moving averages.
+pub const DEFAULT_PRICE_HISTORY: usize = 256;
+const MANIPULATION_RECOVERY_SECS: f64 = 90.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaCrossover {
+    Bullish,
+    Bearish,
+}
+
+#[derive(Debug)]
+pub struct TickerState {
+    pub price: f64,
+    pub last_delta: f64,
+    pub history: VecDeque<f64>,
+    pub alerts: Vec<PriceAlert>,
+    fired_alerts: Vec<PriceAlert>,
+    pub events: Vec<ActiveEvent>,
+    pub chart: ChartMode,
+    pub chart_scale: ChartScale,
+    /// Lowest and highest prices seen since this ticker was created or loaded.
+    pub session_low: f64,
+    pub session_high: f64,
+    /// Samples dropped off the front of `history`, so candle buckets stay put as it rolls.
+    trimmed_samples: usize,
+    /// Longest `history` may grow; set from the config.
+    history_cap: usize,
+    time_since_update: Duration,
+    update_interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChartMode {
+    #[default]
+    Line,
+    Candles,
+}
+
+impl ChartMode {
+    pub(super) fn toggled(self) -> Self {
+        match self {
+            ChartMode::Line => ChartMode::Candles,
+            ChartMode::Candles => ChartMode::Line,
+        }
+    }
+}
+
+/// Vertical range the line chart is drawn against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChartScale {
+    /// Stretch to the visible window's min/max.
+    #[default]
+    Auto,
+    /// Anchor to the session's low/high so absolute level stays readable.
+    Session,
+}
+
+impl ChartScale {
+    pub(super) fn toggled(self) -> Self {
+        match self {
+            ChartScale::Auto => ChartScale::Session,
+            ChartScale::Session => ChartScale::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChartScale::Auto => "auto",
+            ChartScale::Session => "session",
+        }
+    }
+}
+
+/// Open/high/low/close of one bucket of consecutive price samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketEventKind {
+    FlashCrash,
+    BullRun,
+    ExchangeHack,
+    /// Player-triggered; never rolled at random.
+    DumpRecovery,
+    /// Player-triggered; never rolled at random.
+    PumpUnwind,
+}
+
+impl MarketEventKind {
+    const ALL: [MarketEventKind; 3] = [
+        MarketEventKind::FlashCrash,
+        MarketEventKind::BullRun,
+        MarketEventKind::ExchangeHack,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MarketEventKind::FlashCrash => "Flash crash",
+            MarketEventKind::BullRun => "Bull run",
+            MarketEventKind::ExchangeHack => "Exchange hack",
+            MarketEventKind::DumpRecovery => "Dump recovery",
+            MarketEventKind::PumpUnwind => "Pump unwind",
+        }
+    }
+
+    pub(super) fn announcement(self) -> &'static str {
+        match self {
+            MarketEventKind::FlashCrash => "Flash crash! Liquidity evaporates across the chain.",
+            MarketEventKind::BullRun => {
+                "Bull run! Whispers of a ghost miner send buyers rushing in."
+            }
+            MarketEventKind::ExchangeHack => {
+                "Exchange hack! Wallets drained, confidence bleeding out."
+            }
+            MarketEventKind::DumpRecovery => "Bargain hunters creep back in after the dump.",
+            MarketEventKind::PumpUnwind => "The pump fades as early buyers cash out.",
+        }
+    }
+
+    /// One-time fractional price change applied when the event starts.
+    fn shock(self) -> f64 {
+        match self {
+            MarketEventKind::FlashCrash => -0.12,
+            MarketEventKind::BullRun => 0.0,
+            MarketEventKind::ExchangeHack => -0.06,
+            MarketEventKind::DumpRecovery | MarketEventKind::PumpUnwind => 0.0,
+        }
+    }
+
+    /// Extra fractional drift added to every random-walk step while active.
+    fn drift(self) -> f64 {
+        match self {
+            MarketEventKind::FlashCrash => 0.006,
+            MarketEventKind::BullRun => 0.012,
+            MarketEventKind::ExchangeHack => -0.008,
+            MarketEventKind::DumpRecovery => 0.015,
+            MarketEventKind::PumpUnwind => -0.015,
+        }
+    }
+
+    fn duration_range_secs(self) -> (f64, f64) {
+        match self {
+            MarketEventKind::FlashCrash => (45.0, 75.0),
+            MarketEventKind::BullRun => (90.0, 150.0),
+            MarketEventKind::ExchangeHack => (60.0, 120.0),
+            MarketEventKind::DumpRecovery | MarketEventKind::PumpUnwind => {
+                (MANIPULATION_RECOVERY_SECS, MANIPULATION_RECOVERY_SECS)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveEvent {
+    pub kind: MarketEventKind,
+    pub remaining_secs: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub direction: AlertDirection,
+    pub price: f64,
+    pub rearm: bool,
+    armed: bool,
+}
+
+impl AlertDirection {
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        }
+    }
+}
+
+impl PriceAlert {
+    pub(super) fn new(target: f64, market_price: f64) -> Self {
+        let direction = if target >= market_price {
+            AlertDirection::Above
+        } else {
+            AlertDirection::Below
+        };
+        Self {
+            direction,
+            price: target,
+            rearm: false,
+            armed: true,
+        }
+    }
+
+    pub(super) fn retarget(&mut self, target: f64, market_price: f64) {
+        let rearm = self.rearm;
+        *self = Self::new(target, market_price);
+        self.rearm = rearm;
+    }
+
+    fn crossed(&self, previous: f64, current: f64) -> bool {
+        match self.direction {
+            AlertDirection::Above => previous < self.price && current >= self.price,
+            AlertDirection::Below => previous > self.price && current <= self.price,
+        }
+    }
+
+    fn back_across(&self, current: f64) -> bool {
+        match self.direction {
+            AlertDirection::Above => current < self.price,
+            AlertDirection::Below => current > self.price,
+        }
+    }
+}
+
+impl TickerState {
+    pub(super) fn new(initial_price: f64, history_cap: usize, rng: &mut StdRng) -> Self {
+        let mut history = VecDeque::new();
+        history.push_back(initial_price);
+        Self {
+            price: initial_price,
+            last_delta: 0.0,
+            history,
+            alerts: Vec::new(),
+            fired_alerts: Vec::new(),
+            events: Vec::new(),
+            chart: ChartMode::default(),
+            chart_scale: ChartScale::default(),
+            session_low: initial_price,
+            session_high: initial_price,
+            trimmed_samples: 0,
+            history_cap,
+            time_since_update: Duration::ZERO,
+            update_interval: Self::roll_interval(rng),
+        }
+    }
+
+    pub(super) fn tick(&mut self, dt: Duration, rng: &mut StdRng) {
+        self.time_since_update += dt;
+        while self.time_since_update >= self.update_interval {
+            self.time_since_update -= self.update_interval;
+            self.apply_random_walk(rng);
+            self.update_interval = Self::roll_interval(rng);
+        }
+        let secs = dt.as_secs_f64();
+        for event in self.events.iter_mut() {
+            event.remaining_secs -= secs;
+        }
+        self.events.retain(|event| event.remaining_secs > 0.0);
+    }
+
+    /// Occasionally starts a scripted market event, applying its one-time shock.
+    pub(super) fn roll_event(&mut self, dt: Duration, rng: &mut StdRng) -> Option<MarketEventKind> {
+        if !self.events.is_empty() {
+            return None;
+        }
+        let chance = MARKET_EVENT_CHANCE_PER_SEC * dt.as_secs_f64();
+        if !rng.gen_bool(chance.clamp(0.0, 1.0)) {
+            return None;
+        }
+        let kind = *MarketEventKind::ALL.choose(rng)?;
+        let (min, max) = kind.duration_range_secs();
+        self.events.push(ActiveEvent {
+            kind,
+            remaining_secs: rng.gen_range(min..=max),
+        });
+        let shock = kind.shock();
+        if shock.abs() > f64::EPSILON {
+            let new_price = (self.price * (1.0 + shock)).max(PRICE_FLOOR);
+            self.last_delta = new_price - self.price;
+            self.price = new_price;
+            self.record_price();
+        }
+        Some(kind)
+    }
+
+    /// Swings the price by the fractional `shock` and starts a drift back
+    /// toward where it was, replacing any event already running.
+    pub(super) fn manipulate(&mut self, shock: f64) -> MarketEventKind {
+        let kind = if shock < 0.0 {
+            MarketEventKind::DumpRecovery
+        } else {
+            MarketEventKind::PumpUnwind
+        };
+        let (secs, _) = kind.duration_range_secs();
+        self.events = vec![ActiveEvent {
+            kind,
+            remaining_secs: secs,
+        }];
+        let new_price = (self.price * (1.0 + shock)).max(PRICE_FLOOR);
+        self.last_delta = new_price - self.price;
+        self.price = new_price;
+        self.record_price();
+        kind
+    }
+
+    fn event_drift(&self) -> f64 {
+        self.events.iter().map(|event| event.kind.drift()).sum()
+    }
+
+    pub fn update_interval(&self) -> Duration {
+        self.update_interval
+    }
+
+    pub fn seconds_until_update(&self) -> f64 {
+        if self.time_since_update >= self.update_interval {
+            0.0
+        } else {
+            (self.update_interval - self.time_since_update).as_secs_f64()
+        }
+    }
+
+    pub fn moving_average(&self, window: usize) -> Option<f64> {
+        self.moving_average_at(window, 0)
+    }
+
+    /// Average of `window` samples ending `offset` samples before the latest.
+    fn moving_average_at(&self, window: usize, offset: usize) -> Option<f64> {
+        let len = self.history.len();
+        if window == 0 || len < window + offset {
+            return None;
+        }
+        let end = len - offset;
+        let sum: f64 = self.history.range(end - window..end).sum();
+        Some(sum / window as f64)
+    }
+
+    /// Reports whether the short MA crossed the long MA on the latest sample.
+    pub fn ma_crossover(&self) -> Option<MaCrossover> {
+        let short = self.moving_average_at(SHORT_MA_WINDOW, 0)?;
+        let long = self.moving_average_at(LONG_MA_WINDOW, 0)?;
+        let prev_short = self.moving_average_at(SHORT_MA_WINDOW, 1)?;
+        let prev_long = self.moving_average_at(LONG_MA_WINDOW, 1)?;
+        if prev_short <= prev_long && short > long {
+            Some(MaCrossover::Bullish)
+        } else if prev_short >= prev_long && short < long {
+            Some(MaCrossover::Bearish)
+        } else {
+            None
+        }
+    }
+
+    fn roll_interval(rng: &mut StdRng) -> Duration {
+        Duration::from_secs_f64(rng.gen_range(PRICE_UPDATE_MIN_SECS..=PRICE_UPDATE_MAX_SECS))
+    }
+
+    fn apply_random_walk(&mut self, rng: &mut StdRng) {
+        let drift = rng.gen_range(-0.25..0.35);
+        let noise = rng.gen_range(-0.15..0.15);
+        let delta = drift * 0.012 + noise * 0.006 + self.event_drift();
+        let new_price = (self.price * (1.0 + delta)).max(PRICE_FLOOR);
+        self.last_delta = new_price - self.price;
+        self.price = new_price;
+        self.record_price();
+    }
+
+    pub(super) fn apply_market_nudge(&mut self, impact: f64, payout_chain: f64) -> f64 {
+        self.shift_price(market_impulse(impact, payout_chain))
+    }
+
+    /// Moves the price by `delta`, clamped at the floor; returns the actual move.
+    pub(super) fn shift_price(&mut self, delta: f64) -> f64 {
+        let new_price = (self.price + delta).max(PRICE_FLOOR);
+        let delta = new_price - self.price;
+        self.price = new_price;
+        self.last_delta = delta;
+        self.record_price();
+        delta
+    }
+
+    fn record_price(&mut self) {
+        self.session_low = self.session_low.min(self.price);
+        self.session_high = self.session_high.max(self.price);
+        self.history.push_back(self.price);
+        self.trim_history();
+        self.check_alerts(self.price - self.last_delta);
+    }
+
+    fn trim_history(&mut self) {
+        while self.history.len() > self.history_cap.max(1) {
+            self.history.pop_front();
+            self.trimmed_samples += 1;
+        }
+    }
+
+    fn check_alerts(&mut self, previous: f64) {
+        let current = self.price;
+        let mut remaining = Vec::with_capacity(self.alerts.len());
+        for mut alert in std::mem::take(&mut self.alerts) {
+            if alert.armed && alert.crossed(previous, current) {
+                self.fired_alerts.push(alert.clone());
+                if !alert.rearm {
+                    continue;
+                }
+                alert.armed = false;
+            } else if !alert.armed && alert.back_across(current) {
+                alert.armed = true;
+            }
+            remaining.push(alert);
+        }
+        self.alerts = remaining;
+    }
+
+    pub(super) fn take_fired_alerts(&mut self) -> Vec<PriceAlert> {
+        std::mem::take(&mut self.fired_alerts)
+    }
+
+    /// Buckets history into candles of `bucket` samples each, keeping only the
+    /// newest `limit`. The last candle may be partial while its bucket fills;
+    /// a bucket half-trimmed off the front is dropped.
+    pub fn candles(&self, bucket: usize, limit: usize) -> Vec<Candle> {
+        let bucket = bucket.max(1);
+        let skip = (bucket - self.trimmed_samples % bucket) % bucket;
+        let samples: Vec<f64> = self.history.iter().skip(skip).copied().collect();
+        let mut candles: Vec<Candle> = samples
+            .chunks(bucket)
+            .map(|chunk| Candle {
+                open: chunk[0],
+                high: chunk.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                low: chunk.iter().copied().fold(f64::INFINITY, f64::min),
+                close: chunk[chunk.len() - 1],
+            })
+            .collect();
+        if candles.len() > limit {
+            candles.drain(..candles.len() - limit);
+        }
+        candles
+    }
+
+    pub(super) fn to_save(&self) -> TickerSave {
+        TickerSave {
+            price: self.price,
+            last_delta: self.last_delta,
+            history: self.history.iter().copied().collect(),
+            alerts: self.alerts.clone(),
+            events: self.events.clone(),
+            chart: self.chart,
+            chart_scale: self.chart_scale,
+            time_since_update_secs: self.time_since_update.as_secs_f64(),
+            update_interval_secs: self.update_interval.as_secs_f64(),
+            trimmed_samples: self.trimmed_samples,
+        }
+    }
+
+    /// Rebuilds a ticker, trimming saved history to the current config's cap.
+    pub(super) fn from_save(save: TickerSave, history_cap: usize) -> Self {
+        let mut history = VecDeque::from(save.history);
+        if history.is_empty() {
+            history.push_back(save.price);
+        }
+        let session_low = history.iter().copied().fold(save.price, f64::min);
+        let session_high = history.iter().copied().fold(save.price, f64::max);
+        let mut state = Self {
+            price: save.price,
+            last_delta: save.last_delta,
+            history,
+            alerts: save.alerts,
+            fired_alerts: Vec::new(),
+            events: save.events,
+            chart: save.chart,
+            chart_scale: save.chart_scale,
+            session_low,
+            session_high,
+            trimmed_samples: save.trimmed_samples,
+            history_cap,
+            time_since_update: Duration::from_secs_f64(save.time_since_update_secs.max(0.0)),
+            update_interval: Duration::from_secs_f64(
+                save.update_interval_secs
+                    .clamp(PRICE_UPDATE_MIN_SECS, PRICE_UPDATE_MAX_SECS),
+            ),
+        };
+        if state.time_since_update > state.update_interval {
+            state.time_since_update = state.update_interval;
+        }
+        state.trim_history();
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save_with(history: Vec<f64>, since: f64, interval: f64) -> TickerSave {
+        TickerSave {
+            price: 20.0,
+            last_delta: 0.0,
+            history,
+            alerts: Vec::new(),
+            events: Vec::new(),
+            chart: ChartMode::default(),
+            chart_scale: ChartScale::default(),
+            time_since_update_secs: since,
+            update_interval_secs: interval,
+            trimmed_samples: 0,
+        }
+    }
+
+    #[test]
+    fn from_save_clamps_update_timing() {
+        let ticker = TickerState::from_save(save_with(vec![20.0], -3.0, 600.0), 64);
+        assert_eq!(
+            ticker.update_interval,
+            Duration::from_secs_f64(PRICE_UPDATE_MAX_SECS)
+        );
+        assert_eq!(ticker.time_since_update, Duration::ZERO);
+
+        let ticker = TickerState::from_save(save_with(vec![20.0], 40.0, 0.0), 64);
+        assert_eq!(
+            ticker.update_interval,
+            Duration::from_secs_f64(PRICE_UPDATE_MIN_SECS)
+        );
+        assert_eq!(ticker.time_since_update, ticker.update_interval);
+    }
+
+    #[test]
+    fn from_save_trims_history_to_cap() {
+        let history: Vec<f64> = (0..100).map(f64::from).collect();
+        let ticker = TickerState::from_save(save_with(history, 0.0, 10.0), 64);
+        assert_eq!(ticker.history.len(), 64);
+        assert_eq!(ticker.history.front(), Some(&36.0));
+        assert_eq!(ticker.session_low, 0.0);
+        assert_eq!(ticker.session_high, 99.0);
+    }
+
+    #[test]
+    fn candles_keep_their_buckets_across_a_save() {
+        let history: Vec<f64> = (0..70).map(f64::from).collect();
+        let ticker = TickerState::from_save(save_with(history, 0.0, 10.0), 64);
+        let restored = TickerState::from_save(ticker.to_save(), 64);
+        let opens = |ticker: &TickerState| {
+            ticker
+                .candles(4, 100)
+                .iter()
+                .map(|candle| candle.open)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(opens(&restored), opens(&ticker));
+        assert_eq!(opens(&restored)[0], 8.0);
+    }
+
+    #[test]
+    fn from_save_seeds_empty_history_with_price() {
+        let ticker = TickerState::from_save(save_with(Vec::new(), 0.0, 10.0), 64);
+        assert_eq!(ticker.history, VecDeque::from([20.0]));
+    }
+
+    #[test]
+    fn shift_price_stops_at_floor() {
+        let mut ticker = TickerState::from_save(save_with(vec![20.0], 0.0, 10.0), 64);
+        let moved = ticker.shift_price(-100.0);
+        assert_eq!(ticker.price, PRICE_FLOOR);
+        assert_eq!(moved, PRICE_FLOOR - 20.0);
+    }
+
+    #[test]
+    fn one_shot_alert_fires_once() {
+        let mut ticker = TickerState::from_save(save_with(vec![20.0], 0.0, 10.0), 64);
+        ticker.alerts.push(PriceAlert::new(25.0, ticker.price));
+        ticker.shift_price(10.0);
+        assert_eq!(ticker.take_fired_alerts().len(), 1);
+        assert!(ticker.alerts.is_empty());
+        ticker.shift_price(-10.0);
+        ticker.shift_price(10.0);
+        assert!(ticker.take_fired_alerts().is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+
+use crate::app::{DEFAULT_EXCHANGE_SPREAD, DEFAULT_MARKET_DEPTH, DEFAULT_PRICE_HISTORY};
+
+pub const CONFIG_FILE: &str = "blockgrave.toml";
+
+/// Widest board the Active Link pane can draw at the minimum terminal width
+/// once glyph spacing is dropped.
+const MAX_GRID_COLS: usize = 20;
+const MAX_GRID_ROWS: usize = 12;
+const MAX_JOB_POOL_SIZE: usize = 12;
+/// Widest exchange spread accepted; beyond this, selling stops being worthwhile.
+const MAX_SPREAD: f64 = 0.5;
+/// Longest price history accepted; every sample is kept in memory and the save.
+const MAX_PRICE_HISTORY: usize = 4096;
+/// Enough samples for the long moving average to cross over.
+const MIN_PRICE_HISTORY: usize = 64;
+
+/// Inclusive ranges a contract's linklet grid is rolled from.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GridBounds {
+    pub min_rows: usize,
+    pub max_rows: usize,
+    pub min_cols: usize,
+    pub max_cols: usize,
+}
+
+impl Default for GridBounds {
+    fn default() -> Self {
+        Self {
+            min_rows: 3,
+            max_rows: 6,
+            min_cols: 4,
+            max_cols: 8,
+        }
+    }
+}
+
+/// Gameplay tunables read once at startup. Every field falls back to the
+/// built-in value when left out of the file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub job_pool_size: usize,
+    pub grid: GridBounds,
+    /// Append the message feed to `blockgrave.log`; also enabled by `--log`.
+    pub log_activity: bool,
+    /// Exchange spread as a fraction, e.g. 0.05 for a 5% hardcore market.
+    pub spread: f64,
+    /// Order value, in credits, that would move a price by 100%; larger is a
+    /// deeper market with less slippage. Zero turns price impact off.
+    pub market_depth: f64,
+    /// Price samples kept per market for the chart and indicators.
+    pub price_history: usize,
+    /// Ring the terminal bell on Mythic contracts, fired alerts, reached
+    /// goals and credits running dry, for play in a background terminal.
+    pub bell: bool,
+    /// Pay the savings rate on idle credits. Loan interest is charged either way.
+    pub savings_interest: bool,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            job_pool_size: 4,
+            grid: GridBounds::default(),
+            log_activity: false,
+            spread: DEFAULT_EXCHANGE_SPREAD,
+            market_depth: DEFAULT_MARKET_DEPTH,
+            price_history: DEFAULT_PRICE_HISTORY,
+            bell: false,
+            savings_interest: true,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Loads `path`. A missing file yields the defaults silently; a malformed
+    /// or out-of-range one yields the defaults plus a warning for the message feed.
+    pub fn load_or_default(path: impl AsRef<Path>) -> (Self, Option<String>) {
+        let path = path.as_ref();
+        if !path.exists() {
+            return (Self::default(), None);
+        }
+        match Self::load(path) {
+            Ok(config) => (config, None),
+            Err(err) => (Self::default(), Some(format!("Config ignored: {:#}", err))),
+        }
+    }
+
+    /// The shared rules for a daily run: every setting that shapes jobs or
+    /// markets reverts to its default, while logging and the bell carry over.
+    pub fn daily_rules(&self) -> Self {
+        Self {
+            log_activity: self.log_activity,
+            bell: self.bell,
+            ..Self::default()
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let config: Self =
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !(1..=MAX_JOB_POOL_SIZE).contains(&self.job_pool_size) {
+            return Err(anyhow!(
+                "job_pool_size must be between 1 and {}",
+                MAX_JOB_POOL_SIZE
+            ));
+        }
+        if !(0.0..=MAX_SPREAD).contains(&self.spread) {
+            return Err(anyhow!("spread must be between 0 and {}", MAX_SPREAD));
+        }
+        if !(self.market_depth >= 0.0 && self.market_depth.is_finite()) {
+            return Err(anyhow!("market_depth must be zero or a positive number"));
+        }
+        if !(MIN_PRICE_HISTORY..=MAX_PRICE_HISTORY).contains(&self.price_history) {
+            return Err(anyhow!(
+                "price_history must be between {} and {}",
+                MIN_PRICE_HISTORY,
+                MAX_PRICE_HISTORY
+            ));
+        }
+        let grid = &self.grid;
+        if grid.min_rows == 0 || grid.min_rows > grid.max_rows || grid.max_rows > MAX_GRID_ROWS {
+            return Err(anyhow!(
+                "grid rows must satisfy 1 <= min_rows <= max_rows <= {}",
+                MAX_GRID_ROWS
+            ));
+        }
+        if grid.min_cols == 0 || grid.min_cols > grid.max_cols || grid.max_cols > MAX_GRID_COLS {
+            return Err(anyhow!(
+                "grid cols must satisfy 1 <= min_cols <= max_cols <= {}",
+                MAX_GRID_COLS
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+
+/// Net worth a daily run has to reach.
+pub const DAILY_GOAL_NET_WORTH: f64 = 5_000.0;
+pub const DAILY_TIME_LIMIT_SECS: f64 = 15.0 * 60.0;
+/// Score awarded per second left on the clock when the goal is reached early.
+const DAILY_TIME_BONUS_PER_SEC: f64 = 10.0;
+
+/// Every player launching `--daily` on the same UTC date gets this seed.
+pub fn seed_for(date: NaiveDate) -> u64 {
+    let hash = blake3::hash(format!("blockgrave-daily-{}", date.format("%Y-%m-%d")).as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DailyOutcome {
+    GoalReached { secs: f64 },
+    TimeUp,
+}
+
+/// A timed run on the date's shared seed. Play stops once it has an outcome.
+#[derive(Debug, Clone)]
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    pub elapsed_secs: f64,
+    pub outcome: Option<DailyOutcome>,
+    pub score: u64,
+}
+
+impl DailyChallenge {
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            elapsed_secs: 0.0,
+            outcome: None,
+            score: 0,
+        }
+    }
+
+    pub fn remaining_secs(&self) -> f64 {
+        (DAILY_TIME_LIMIT_SECS - self.elapsed_secs).max(0.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Advances the clock and settles the run once the goal is met or time
+    /// runs out. Returns the outcome on the tick it is decided.
+    pub fn advance(&mut self, secs: f64, net_worth: f64) -> Option<DailyOutcome> {
+        if self.is_finished() {
+            return None;
+        }
+        self.elapsed_secs += secs;
+        let outcome = if net_worth >= DAILY_GOAL_NET_WORTH {
+            // Finishing early always outscores running out the clock.
+            self.score = (DAILY_GOAL_NET_WORTH + self.remaining_secs() * DAILY_TIME_BONUS_PER_SEC)
+                .round() as u64;
+            DailyOutcome::GoalReached {
+                secs: self.elapsed_secs,
+            }
+        } else if self.remaining_secs() <= 0.0 {
+            self.score = net_worth.max(0.0).round() as u64;
+            DailyOutcome::TimeUp
+        } else {
+            return None;
+        };
+        self.outcome = Some(outcome);
+        Some(outcome)
+    }
+}
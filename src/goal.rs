@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+/// What a "mine until" goal measures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GoalMetric {
+    /// Chain held in the bank, not counting staked chain.
+    #[default]
+    Chain,
+    NetWorth,
+}
+
+impl GoalMetric {
+    pub fn label(self) -> &'static str {
+        match self {
+            GoalMetric::Chain => "Chain",
+            GoalMetric::NetWorth => "Net worth",
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            GoalMetric::Chain => "⛓",
+            GoalMetric::NetWorth => "₵",
+        }
+    }
+
+    /// Amount one ←/→ press moves the target by.
+    pub fn step(self) -> f64 {
+        match self {
+            GoalMetric::Chain => 50.0,
+            GoalMetric::NetWorth => 1_000.0,
+        }
+    }
+
+    pub fn toggled(self) -> Self {
+        match self {
+            GoalMetric::Chain => GoalMetric::NetWorth,
+            GoalMetric::NetWorth => GoalMetric::Chain,
+        }
+    }
+}
+
+/// What happens once the goal is met, on top of the announcement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GoalCompletion {
+    #[default]
+    Announce,
+    Pause,
+    Save,
+}
+
+impl GoalCompletion {
+    pub fn label(self) -> &'static str {
+        match self {
+            GoalCompletion::Announce => "announce",
+            GoalCompletion::Pause => "pause",
+            GoalCompletion::Save => "save",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            GoalCompletion::Announce => GoalCompletion::Pause,
+            GoalCompletion::Pause => GoalCompletion::Save,
+            GoalCompletion::Save => GoalCompletion::Announce,
+        }
+    }
+}
+
+/// "Mine until" target for idle sessions. Cleared once it is reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    pub metric: GoalMetric,
+    pub target: f64,
+    #[serde(default)]
+    pub on_reach: GoalCompletion,
+}
+
+impl Goal {
+    /// A goal on `metric` set one step past the next round number above `current`.
+    pub fn above(metric: GoalMetric, current: f64) -> Self {
+        let step = metric.step();
+        Self {
+            metric,
+            target: ((current.max(0.0) / step).floor() + 1.0) * step,
+            on_reach: GoalCompletion::default(),
+        }
+    }
+
+    pub fn adjust(&mut self, steps: f64) {
+        let step = self.metric.step();
+        self.target = (self.target + steps * step).max(step);
+    }
+
+    pub fn progress(&self, current: f64) -> f64 {
+        (current / self.target).clamp(0.0, 1.0)
+    }
+}
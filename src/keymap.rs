@@ -0,0 +1,489 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use crossterm::event::{KeyCode, KeyEvent};
+use serde::Deserialize;
+
+pub const KEYMAP_FILE: &str = "blockgrave-keys.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    NextPane,
+    PrevPane,
+    FocusMining,
+    FocusHashpower,
+    FocusBank,
+    FocusLedger,
+    Pause,
+    ToggleStats,
+    ToggleDebug,
+    ToggleZoom,
+    Undo,
+    ToggleChart,
+    ToggleChartScale,
+    SetGoal,
+    Snapshot,
+    MoveUp,
+    MoveDown,
+    AcceptContract,
+    RerollContracts,
+    ToggleAutoAccept,
+    CycleSort,
+    ContractDetails,
+    ToggleStrategy,
+    PurchaseTier,
+    PurchaseTen,
+    PurchaseMax,
+    Overclock,
+    ToggleResearch,
+    SellChain,
+    BuyChain,
+    SellBulk,
+    BuyBulk,
+    SwitchAsset,
+    ToggleAutoSell,
+    LowerAutoSellCap,
+    RaiseAutoSellCap,
+    Stake,
+    Unstake,
+    Borrow,
+    Repay,
+    Dump,
+    Pump,
+    NewLimitOrder,
+    CancelLimitOrder,
+    NewAlert,
+    ClearAlerts,
+    SearchLedger,
+    CopyLinkId,
+    PageUp,
+    PageDown,
+    JumpNewest,
+    JumpOldest,
+    DraftLower,
+    DraftRaise,
+    ToggleOrderSide,
+    ToggleRearm,
+    ToggleGoalMetric,
+    CycleGoalOnReach,
+    ClearGoal,
+}
+
+/// Which handler an action belongs to; actions in different scopes may share a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Global,
+    Navigation,
+    Mining,
+    Hashpower,
+    Bank,
+    Ledger,
+    /// Limit order, alert and goal editors, which capture keys while open.
+    Draft,
+}
+
+impl Action {
+    const ALL: [Action; 59] = [
+        Action::NextPane,
+        Action::PrevPane,
+        Action::FocusMining,
+        Action::FocusHashpower,
+        Action::FocusBank,
+        Action::FocusLedger,
+        Action::Pause,
+        Action::ToggleStats,
+        Action::ToggleDebug,
+        Action::ToggleZoom,
+        Action::Undo,
+        Action::ToggleChart,
+        Action::ToggleChartScale,
+        Action::SetGoal,
+        Action::Snapshot,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::AcceptContract,
+        Action::RerollContracts,
+        Action::ToggleAutoAccept,
+        Action::CycleSort,
+        Action::ContractDetails,
+        Action::ToggleStrategy,
+        Action::PurchaseTier,
+        Action::PurchaseTen,
+        Action::PurchaseMax,
+        Action::Overclock,
+        Action::ToggleResearch,
+        Action::SellChain,
+        Action::BuyChain,
+        Action::SellBulk,
+        Action::BuyBulk,
+        Action::SwitchAsset,
+        Action::ToggleAutoSell,
+        Action::LowerAutoSellCap,
+        Action::RaiseAutoSellCap,
+        Action::Stake,
+        Action::Unstake,
+        Action::Borrow,
+        Action::Repay,
+        Action::Dump,
+        Action::Pump,
+        Action::NewLimitOrder,
+        Action::CancelLimitOrder,
+        Action::NewAlert,
+        Action::ClearAlerts,
+        Action::SearchLedger,
+        Action::CopyLinkId,
+        Action::PageUp,
+        Action::PageDown,
+        Action::JumpNewest,
+        Action::JumpOldest,
+        Action::DraftLower,
+        Action::DraftRaise,
+        Action::ToggleOrderSide,
+        Action::ToggleRearm,
+        Action::ToggleGoalMetric,
+        Action::CycleGoalOnReach,
+        Action::ClearGoal,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Action::NextPane => "next_pane",
+            Action::PrevPane => "prev_pane",
+            Action::FocusMining => "focus_mining",
+            Action::FocusHashpower => "focus_hashpower",
+            Action::FocusBank => "focus_bank",
+            Action::FocusLedger => "focus_ledger",
+            Action::Pause => "pause",
+            Action::ToggleStats => "toggle_stats",
+            Action::ToggleDebug => "toggle_debug",
+            Action::ToggleZoom => "toggle_zoom",
+            Action::Undo => "undo",
+            Action::ToggleChart => "toggle_chart",
+            Action::ToggleChartScale => "toggle_chart_scale",
+            Action::SetGoal => "set_goal",
+            Action::Snapshot => "snapshot",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::AcceptContract => "accept_contract",
+            Action::RerollContracts => "reroll_contracts",
+            Action::ToggleAutoAccept => "toggle_auto_accept",
+            Action::CycleSort => "cycle_sort",
+            Action::ContractDetails => "contract_details",
+            Action::ToggleStrategy => "toggle_strategy",
+            Action::PurchaseTier => "purchase_tier",
+            Action::PurchaseTen => "purchase_ten",
+            Action::PurchaseMax => "purchase_max",
+            Action::Overclock => "overclock",
+            Action::ToggleResearch => "toggle_research",
+            Action::SellChain => "sell_chain",
+            Action::BuyChain => "buy_chain",
+            Action::SellBulk => "sell_bulk",
+            Action::BuyBulk => "buy_bulk",
+            Action::SwitchAsset => "switch_asset",
+            Action::ToggleAutoSell => "toggle_auto_sell",
+            Action::LowerAutoSellCap => "lower_auto_sell_cap",
+            Action::RaiseAutoSellCap => "raise_auto_sell_cap",
+            Action::Stake => "stake",
+            Action::Unstake => "unstake",
+            Action::Borrow => "borrow",
+            Action::Repay => "repay",
+            Action::Dump => "dump",
+            Action::Pump => "pump",
+            Action::NewLimitOrder => "new_limit_order",
+            Action::CancelLimitOrder => "cancel_limit_order",
+            Action::NewAlert => "new_alert",
+            Action::ClearAlerts => "clear_alerts",
+            Action::SearchLedger => "search_ledger",
+            Action::CopyLinkId => "copy_link_id",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::JumpNewest => "jump_newest",
+            Action::JumpOldest => "jump_oldest",
+            Action::DraftLower => "draft_lower",
+            Action::DraftRaise => "draft_raise",
+            Action::ToggleOrderSide => "toggle_order_side",
+            Action::ToggleRearm => "toggle_rearm",
+            Action::ToggleGoalMetric => "toggle_goal_metric",
+            Action::CycleGoalOnReach => "cycle_goal_on_reach",
+            Action::ClearGoal => "clear_goal",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    fn scope(self) -> Scope {
+        match self {
+            Action::NextPane
+            | Action::PrevPane
+            | Action::FocusMining
+            | Action::FocusHashpower
+            | Action::FocusBank
+            | Action::FocusLedger
+            | Action::Pause
+            | Action::ToggleStats
+            | Action::ToggleDebug
+            | Action::ToggleZoom
+            | Action::Undo
+            | Action::ToggleChart
+            | Action::ToggleChartScale
+            | Action::SetGoal
+            | Action::Snapshot => Scope::Global,
+            Action::MoveUp | Action::MoveDown => Scope::Navigation,
+            Action::AcceptContract
+            | Action::RerollContracts
+            | Action::ToggleAutoAccept
+            | Action::CycleSort
+            | Action::ContractDetails
+            | Action::ToggleStrategy => Scope::Mining,
+            Action::PurchaseTier
+            | Action::PurchaseTen
+            | Action::PurchaseMax
+            | Action::Overclock
+            | Action::ToggleResearch => Scope::Hashpower,
+            Action::SellChain
+            | Action::BuyChain
+            | Action::SellBulk
+            | Action::BuyBulk
+            | Action::SwitchAsset
+            | Action::ToggleAutoSell
+            | Action::LowerAutoSellCap
+            | Action::RaiseAutoSellCap
+            | Action::Stake
+            | Action::Unstake
+            | Action::Borrow
+            | Action::Repay
+            | Action::Dump
+            | Action::Pump
+            | Action::NewLimitOrder
+            | Action::CancelLimitOrder
+            | Action::NewAlert
+            | Action::ClearAlerts => Scope::Bank,
+            Action::SearchLedger
+            | Action::CopyLinkId
+            | Action::PageUp
+            | Action::PageDown
+            | Action::JumpNewest
+            | Action::JumpOldest => Scope::Ledger,
+            Action::DraftLower
+            | Action::DraftRaise
+            | Action::ToggleOrderSide
+            | Action::ToggleRearm
+            | Action::ToggleGoalMetric
+            | Action::CycleGoalOnReach
+            | Action::ClearGoal => Scope::Draft,
+        }
+    }
+
+    fn default_key(self) -> KeyCode {
+        match self {
+            Action::NextPane => KeyCode::Tab,
+            Action::PrevPane => KeyCode::BackTab,
+            Action::FocusMining => KeyCode::Char('1'),
+            Action::FocusHashpower => KeyCode::Char('2'),
+            Action::FocusBank => KeyCode::Char('3'),
+            Action::FocusLedger => KeyCode::Char('4'),
+            Action::Pause => KeyCode::Char('q'),
+            Action::ToggleStats => KeyCode::Char('i'),
+            Action::ToggleDebug => KeyCode::F(12),
+            Action::ToggleZoom => KeyCode::Char('z'),
+            Action::Undo => KeyCode::Char('u'),
+            Action::ToggleChart => KeyCode::Char('k'),
+            Action::ToggleChartScale => KeyCode::Char('v'),
+            Action::SetGoal => KeyCode::Char('g'),
+            Action::Snapshot => KeyCode::F(2),
+            Action::MoveUp => KeyCode::Up,
+            Action::MoveDown => KeyCode::Down,
+            Action::AcceptContract => KeyCode::Enter,
+            Action::RerollContracts => KeyCode::Char('r'),
+            Action::ToggleAutoAccept => KeyCode::Char('a'),
+            Action::CycleSort => KeyCode::Char('s'),
+            Action::ContractDetails => KeyCode::Char('d'),
+            Action::ToggleStrategy => KeyCode::Char('w'),
+            Action::PurchaseTier => KeyCode::Enter,
+            Action::PurchaseTen => KeyCode::Char('x'),
+            Action::PurchaseMax => KeyCode::Char('m'),
+            Action::Overclock => KeyCode::Char('o'),
+            Action::ToggleResearch => KeyCode::Char('r'),
+            Action::SellChain => KeyCode::Left,
+            Action::BuyChain => KeyCode::Right,
+            Action::SellBulk => KeyCode::Char('m'),
+            Action::BuyBulk => KeyCode::Char('b'),
+            Action::SwitchAsset => KeyCode::Char('t'),
+            Action::ToggleAutoSell => KeyCode::Char('e'),
+            Action::LowerAutoSellCap => KeyCode::Char('['),
+            Action::RaiseAutoSellCap => KeyCode::Char(']'),
+            Action::Stake => KeyCode::Char('s'),
+            Action::Unstake => KeyCode::Char('w'),
+            Action::Borrow => KeyCode::Char('l'),
+            Action::Repay => KeyCode::Char('r'),
+            Action::Dump => KeyCode::Char('d'),
+            Action::Pump => KeyCode::Char('p'),
+            Action::NewLimitOrder => KeyCode::Char('o'),
+            Action::CancelLimitOrder => KeyCode::Char('x'),
+            Action::NewAlert => KeyCode::Char('a'),
+            Action::ClearAlerts => KeyCode::Char('c'),
+            Action::SearchLedger => KeyCode::Char('/'),
+            Action::CopyLinkId => KeyCode::Char('c'),
+            Action::PageUp => KeyCode::PageUp,
+            Action::PageDown => KeyCode::PageDown,
+            Action::JumpNewest => KeyCode::Home,
+            Action::JumpOldest => KeyCode::End,
+            Action::DraftLower => KeyCode::Left,
+            Action::DraftRaise => KeyCode::Right,
+            Action::ToggleOrderSide => KeyCode::Char('s'),
+            Action::ToggleRearm => KeyCode::Char('r'),
+            Action::ToggleGoalMetric => KeyCode::Char('t'),
+            Action::CycleGoalOnReach => KeyCode::Char('a'),
+            Action::ClearGoal => KeyCode::Char('x'),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct KeymapFile {
+    #[serde(default)]
+    keys: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<Action, KeyCode>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| (action, action.default_key()))
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Loads overrides from `path`. A missing file yields the defaults silently;
+    /// a malformed one yields the defaults plus a warning for the message feed.
+    pub fn load_or_default(path: impl AsRef<Path>) -> (Self, Option<String>) {
+        let path = path.as_ref();
+        if !path.exists() {
+            return (Self::default(), None);
+        }
+        match Self::load(path) {
+            Ok(keymap) => (keymap, None),
+            Err(err) => (
+                Self::default(),
+                Some(format!("Key bindings ignored: {:#}", err)),
+            ),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let file: KeymapFile =
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        let mut keymap = Self::default();
+        for (name, key) in file.keys {
+            let action =
+                Action::from_name(&name).ok_or_else(|| anyhow!("unknown action '{}'", name))?;
+            let code =
+                parse_key(&key).ok_or_else(|| anyhow!("unknown key '{}' for {}", key, name))?;
+            keymap.bindings.insert(action, code);
+        }
+        Ok(keymap)
+    }
+
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.bindings
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_key())
+    }
+
+    pub fn matches(&self, action: Action, key: &KeyEvent) -> bool {
+        codes_match(self.key(action), key.code)
+    }
+
+    /// Resolves `key` to the first action bound within `scopes`, in order.
+    pub fn action(&self, scopes: &[Scope], key: &KeyEvent) -> Option<Action> {
+        scopes.iter().find_map(|&scope| {
+            Action::ALL
+                .into_iter()
+                .filter(|action| action.scope() == scope)
+                .find(|&action| self.matches(action, key))
+        })
+    }
+
+    pub fn label(&self, action: Action) -> String {
+        key_label(self.key(action))
+    }
+}
+
+fn codes_match(bound: KeyCode, pressed: KeyCode) -> bool {
+    match (bound, pressed) {
+        (KeyCode::Char(a), KeyCode::Char(b)) => a.eq_ignore_ascii_case(&b),
+        (a, b) => a == b,
+    }
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c.to_ascii_lowercase()));
+    }
+    let code = match name.to_ascii_lowercase().as_str() {
+        "enter" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "insert" => KeyCode::Insert,
+        "delete" => KeyCode::Delete,
+        other => {
+            let number = other.strip_prefix('f')?.parse::<u8>().ok()?;
+            if (1..=12).contains(&number) {
+                KeyCode::F(number)
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(code)
+}
+
+fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::Insert => "Ins".to_string(),
+        KeyCode::Delete => "Del".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
@@ -1,45 +1,150 @@
+mod achievements;
+mod activity_log;
 mod app;
+mod config;
+mod daily;
+mod goal;
+mod keymap;
+mod preset;
+mod sim;
+mod snapshot;
+mod theme;
 mod ui;
 
 use std::io::{self, Stdout};
 use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result, anyhow};
 use app::App;
+use chrono::Utc;
 
-use crossterm::event::{self, Event as CEvent};
+use crossterm::cursor::Show;
+use crossterm::event::{
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+    Event as CEvent, MouseEvent,
+};
 #[cfg(not(windows))]
 use crossterm::event::{
     KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
+use crossterm::style::Print;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 
+use crate::preset::Preset;
 use crate::ui::draw;
 
+const TICK_RATE: Duration = Duration::from_millis(200);
+const DEFAULT_SIM_SEED: u64 = 0;
+
 enum Event<I> {
     Input(I),
+    Mouse(MouseEvent),
+    Focus(bool),
+    Resize,
     Tick,
 }
 
+#[derive(Debug, Default)]
+struct CliArgs {
+    seed: Option<u64>,
+    simulate: Option<u64>,
+    log: bool,
+    daily: bool,
+    preset: Option<Preset>,
+}
+
+fn parse_args() -> Result<CliArgs> {
+    let mut args = CliArgs::default();
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--seed requires a value"))?;
+                let seed = value
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid seed '{}'", value))?;
+                args.seed = Some(seed);
+            }
+            "--simulate" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--simulate requires a tick count"))?;
+                let ticks = value
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid tick count '{}'", value))?;
+                args.simulate = Some(ticks);
+            }
+            "--preset" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--preset requires easy, normal or hardcore"))?;
+                args.preset = Some(Preset::parse(&value)?);
+            }
+            "--log" => args.log = true,
+            "--daily" => args.daily = true,
+            other => return Err(anyhow!("unrecognised argument '{}'", other)),
+        }
+    }
+    if args.daily && (args.seed.is_some() || args.simulate.is_some()) {
+        return Err(anyhow!(
+            "--daily picks its own seed and can't be combined with --seed or --simulate"
+        ));
+    }
+    if args.daily && args.preset.is_some() {
+        return Err(anyhow!("--daily always runs on the Normal preset"));
+    }
+    Ok(args)
+}
+
 fn main() -> Result<()> {
-    let mut app = App::new()?;
+    let args = parse_args()?;
+    if let Some(ticks) = args.simulate {
+        return run_simulation(
+            ticks,
+            args.seed.unwrap_or(DEFAULT_SIM_SEED),
+            args.preset.unwrap_or_default(),
+        );
+    }
+
+    let mut app = if args.daily {
+        App::new_daily(Utc::now().date_naive())?
+    } else {
+        App::new(args.seed, args.preset.unwrap_or_default())?
+    };
+    if args.log {
+        app.enable_activity_log();
+    }
     let (mut terminal, keyboard_enhanced) = setup_terminal()?;
+    install_panic_hook(keyboard_enhanced);
     let res = run_app(&mut terminal, &mut app);
     restore_terminal(&mut terminal, keyboard_enhanced)?;
     res
 }
 
+fn run_simulation(ticks: u64, seed: u64, preset: Preset) -> Result<()> {
+    let report = sim::run(ticks, seed, preset, TICK_RATE)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
 fn setup_terminal() -> Result<(Terminal<CrosstermBackend<Stdout>>, bool)> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(&mut stdout, EnterAlternateScreen)?;
+    execute!(
+        &mut stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
     let keyboard_enhanced = try_enable_keyboard_enhancement(&mut stdout)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
@@ -58,12 +163,41 @@ fn restore_terminal(
     if keyboard_enhanced {
         execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
     }
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
 
     terminal.show_cursor()?;
     Ok(())
 }
 
+/// Puts the terminal back before the default hook prints the panic message,
+/// so the backtrace lands on a usable screen instead of the alternate one.
+fn install_panic_hook(keyboard_enhanced: bool) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let mut stdout = io::stdout();
+        #[cfg(windows)]
+        let _ = keyboard_enhanced;
+        #[cfg(not(windows))]
+        if keyboard_enhanced {
+            let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(
+            stdout,
+            DisableFocusChange,
+            DisableMouseCapture,
+            LeaveAlternateScreen,
+            Show
+        );
+        default_hook(info);
+    }));
+}
+
 #[cfg(windows)]
 fn try_enable_keyboard_enhancement(_: &mut Stdout) -> Result<bool> {
     Ok(false)
@@ -104,7 +238,7 @@ fn keyboard_enhancement_unsupported(_: &std::io::Error) -> bool {
 
 fn run_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) -> Result<()> {
     let (tx, rx) = mpsc::channel();
-    let tick_rate = Duration::from_millis(200);
+    let tick_rate = TICK_RATE;
 
     let input_tx = tx.clone();
     thread::spawn(move || {
@@ -118,6 +252,26 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
                         break;
                     }
                 }
+                Ok(CEvent::Mouse(mouse)) => {
+                    if input_tx.send(Event::Mouse(mouse)).is_err() {
+                        break;
+                    }
+                }
+                Ok(CEvent::FocusGained) => {
+                    if input_tx.send(Event::Focus(true)).is_err() {
+                        break;
+                    }
+                }
+                Ok(CEvent::FocusLost) => {
+                    if input_tx.send(Event::Focus(false)).is_err() {
+                        break;
+                    }
+                }
+                Ok(CEvent::Resize(_, _)) => {
+                    if input_tx.send(Event::Resize).is_err() {
+                        break;
+                    }
+                }
                 Ok(_) => {}
                 Err(_) => {}
             }
@@ -134,17 +288,39 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App) ->
     });
 
     loop {
+        let draw_started = Instant::now();
         terminal.draw(|f| draw(f, app))?;
+        app.record_draw_time(draw_started.elapsed());
 
         match rx.recv()? {
             Event::Input(key) => {
                 app.on_key(key);
             }
+            Event::Mouse(mouse) => {
+                app.on_mouse(mouse);
+            }
+            Event::Focus(gained) => {
+                app.on_focus_change(gained);
+            }
+            Event::Resize => {
+                // Repaint at the new size straight away instead of on the next tick.
+                terminal.autoresize()?;
+            }
             Event::Tick => {
                 app.on_tick(tick_rate);
             }
         }
 
+        if app.take_snapshot_request() {
+            let size = terminal.size()?;
+            let result = snapshot::capture(app, size.width, size.height);
+            app.report_snapshot(result);
+        }
+
+        if app.take_bell() {
+            execute!(terminal.backend_mut(), Print('\x07'))?;
+        }
+
         if app.should_quit {
             break;
         }
@@ -0,0 +1,74 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// Widest spread a preset can stretch the configured one to.
+const MAX_PRESET_SPREAD: f64 = 0.5;
+
+/// Starting conditions picked with `--preset`. A save remembers the one it
+/// was started under, and prestige resets back to the same start.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Preset {
+    Easy,
+    #[default]
+    Normal,
+    Hardcore,
+}
+
+impl Preset {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Preset::Easy),
+            "normal" => Ok(Preset::Normal),
+            "hardcore" => Ok(Preset::Hardcore),
+            other => Err(anyhow!(
+                "unknown preset '{}' (expected easy, normal or hardcore)",
+                other
+            )),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Preset::Easy => "Easy",
+            Preset::Normal => "Normal",
+            Preset::Hardcore => "Hardcore",
+        }
+    }
+
+    pub fn starting_credits(self) -> f64 {
+        match self {
+            Preset::Easy => 500.0,
+            Preset::Normal => 100.0,
+            // Just enough for a first Processor.
+            Preset::Hardcore => 90.0,
+        }
+    }
+
+    /// Tier index and count of the rigs owned at the start, if any.
+    pub fn starting_rig(self) -> Option<(usize, u32)> {
+        match self {
+            Preset::Easy => Some((1, 1)),
+            Preset::Normal => Some((0, 1)),
+            Preset::Hardcore => None,
+        }
+    }
+
+    /// The configured exchange spread adjusted for this preset.
+    pub fn spread(self, configured: f64) -> f64 {
+        let multiplier = match self {
+            Preset::Easy => 0.5,
+            Preset::Normal => 1.0,
+            Preset::Hardcore => 3.0,
+        };
+        (configured * multiplier).min(MAX_PRESET_SPREAD.max(configured))
+    }
+
+    /// Scales every contract's payout as it is generated.
+    pub fn payout_multiplier(self) -> f64 {
+        match self {
+            Preset::Easy => 1.25,
+            Preset::Normal => 1.0,
+            Preset::Hardcore => 0.8,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::app::{App, Asset, OrderSide, SimulatedClock};
+use crate::preset::Preset;
+
+#[derive(Debug, Serialize)]
+pub struct SimulationReport {
+    pub seed: u64,
+    pub preset: Preset,
+    pub ticks: u64,
+    pub simulated_secs: f64,
+    pub net_worth: f64,
+    pub credits: f64,
+    pub chain: f64,
+    pub final_price: f64,
+    pub average_price: f64,
+    pub links_restored: usize,
+    pub total_power: f64,
+    pub hashpower_owned: Vec<u32>,
+}
+
+/// Drives the economy without a terminal. Contracts are auto-accepted, mined
+/// chain and shards are liquidated immediately, and credits go into the cheapest tier.
+pub fn run(ticks: u64, seed: u64, preset: Preset, dt: Duration) -> Result<SimulationReport> {
+    let mut app = App::new(Some(seed), preset)?;
+    app.autosave_interval = Duration::ZERO;
+    // Ledger timestamps follow simulated time so a seed always replays identically.
+    app.clock = Box::new(SimulatedClock::starting_at(DateTime::UNIX_EPOCH));
+    let mut price_sum = 0.0;
+
+    for _ in 0..ticks {
+        if app.mining.active_job.is_none() {
+            app.accept_selected_job();
+        }
+        for asset in [Asset::Chain, Asset::Shards] {
+            let balance = app.bank.balance(asset);
+            if balance > 0.0 {
+                // Dust below the flat fee can't be sold; it just waits for more.
+                let _ = app.market_order(asset, OrderSide::Sell, balance);
+            }
+        }
+        buy_cheapest_affordable(&mut app);
+        app.on_tick(dt);
+        price_sum += app.ticker.price;
+    }
+
+    let average_price = if ticks > 0 {
+        price_sum / ticks as f64
+    } else {
+        app.ticker.price
+    };
+
+    Ok(SimulationReport {
+        seed,
+        preset,
+        ticks,
+        simulated_secs: dt.as_secs_f64() * ticks as f64,
+        net_worth: app.net_worth(),
+        credits: app.bank.credits_balance,
+        chain: app.bank.chain_balance,
+        final_price: app.ticker.price,
+        average_price,
+        links_restored: app.ledger.entries.len(),
+        total_power: app.hashpower.total_power(),
+        hashpower_owned: app.hashpower.tiers.iter().map(|tier| tier.owned).collect(),
+    })
+}
+
+fn buy_cheapest_affordable(app: &mut App) {
+    loop {
+        let cheapest = app
+            .hashpower
+            .tiers
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| app.hashpower.is_unlocked(idx))
+            .min_by(|(_, a), (_, b)| a.cost_for_next().total_cmp(&b.cost_for_next()))
+            .map(|(idx, _)| idx);
+        let Some(idx) = cheapest else {
+            break;
+        };
+        if app.hashpower.purchase_tier(idx, &mut app.bank).is_none() {
+            break;
+        }
+    }
+}
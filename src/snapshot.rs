@@ -0,0 +1,97 @@
+use std::fmt::Write as _;
+use std::fs;
+
+use anyhow::{Context, Result};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::App;
+use crate::ui::draw;
+
+pub const SNAPSHOT_FILE: &str = "blockgrave-snapshot.txt";
+
+/// Renders the current screen off-screen at `width`×`height` and writes it to
+/// `SNAPSHOT_FILE` as ANSI-coloured text; `cat` it to view. The live terminal
+/// is left alone.
+pub fn capture(app: &App, width: u16, height: u16) -> Result<()> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    let frame = terminal.draw(|f| draw(f, app))?;
+    let text = buffer_to_ansi(frame.buffer);
+    fs::write(SNAPSHOT_FILE, text).with_context(|| format!("writing {}", SNAPSHOT_FILE))?;
+    Ok(())
+}
+
+fn buffer_to_ansi(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    for row in buffer.content.chunks(usize::from(buffer.area.width)) {
+        let mut current: Option<(Color, Color, Modifier)> = None;
+        // Cells under the right half of a wide glyph are padding, not text.
+        let mut skip = 0usize;
+        for cell in row {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+            skip = cell.symbol().width().saturating_sub(1);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Escape sequence that resets and then applies `cell`'s colours and weight.
+fn sgr(cell: &Cell) -> String {
+    let mut codes = String::from("\x1b[0");
+    if cell.modifier.contains(Modifier::BOLD) {
+        codes.push_str(";1");
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        codes.push_str(";2");
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        codes.push_str(";3");
+    }
+    if cell.modifier.contains(Modifier::UNDERLINED) {
+        codes.push_str(";4");
+    }
+    push_color(&mut codes, cell.fg, 30);
+    push_color(&mut codes, cell.bg, 40);
+    codes.push('m');
+    codes
+}
+
+/// Appends the SGR parameters for `color`; `base` is 30 for foreground, 40 for background.
+fn push_color(codes: &mut String, color: Color, base: u8) {
+    let basic = |offset: u8| base + offset;
+    let bright = |offset: u8| base + 60 + offset;
+    let _ = match color {
+        Color::Reset => return,
+        Color::Black => write!(codes, ";{}", basic(0)),
+        Color::Red => write!(codes, ";{}", basic(1)),
+        Color::Green => write!(codes, ";{}", basic(2)),
+        Color::Yellow => write!(codes, ";{}", basic(3)),
+        Color::Blue => write!(codes, ";{}", basic(4)),
+        Color::Magenta => write!(codes, ";{}", basic(5)),
+        Color::Cyan => write!(codes, ";{}", basic(6)),
+        Color::Gray => write!(codes, ";{}", basic(7)),
+        Color::DarkGray => write!(codes, ";{}", bright(0)),
+        Color::LightRed => write!(codes, ";{}", bright(1)),
+        Color::LightGreen => write!(codes, ";{}", bright(2)),
+        Color::LightYellow => write!(codes, ";{}", bright(3)),
+        Color::LightBlue => write!(codes, ";{}", bright(4)),
+        Color::LightMagenta => write!(codes, ";{}", bright(5)),
+        Color::LightCyan => write!(codes, ";{}", bright(6)),
+        Color::White => write!(codes, ";{}", bright(7)),
+        Color::Rgb(r, g, b) => write!(codes, ";{};2;{};{};{}", base + 8, r, g, b),
+        Color::Indexed(index) => write!(codes, ";{};5;{}", base + 8, index),
+    };
+}
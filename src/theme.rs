@@ -0,0 +1,131 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Selectable colour presets, persisted by name in the save file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeKind {
+    #[default]
+    Default,
+    HighContrast,
+    Monochrome,
+}
+
+impl ThemeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeKind::Default => "Default",
+            ThemeKind::HighContrast => "High contrast",
+            ThemeKind::Monochrome => "Monochrome",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ThemeKind::Default => ThemeKind::HighContrast,
+            ThemeKind::HighContrast => ThemeKind::Monochrome,
+            ThemeKind::Monochrome => ThemeKind::Default,
+        }
+    }
+
+    pub fn palette(self) -> &'static Theme {
+        match self {
+            ThemeKind::Default => &DEFAULT,
+            ThemeKind::HighContrast => &HIGH_CONTRAST,
+            ThemeKind::Monochrome => &MONOCHROME,
+        }
+    }
+}
+
+/// The colours every draw function pulls from, grouped by role rather than hue.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub text: Color,
+    pub label: Color,
+    pub muted: Color,
+    pub background: Color,
+    pub border: Color,
+    pub border_focused: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub positive: Color,
+    pub negative: Color,
+    pub secondary: Color,
+    pub tertiary: Color,
+    pub gauge: Color,
+    pub linklet_complete: Color,
+    pub linklet_active: Color,
+    pub linklet_pending: Color,
+    /// Indexed by rarity: Common, Rare, Epic, Mythic.
+    pub rarity: [Color; 4],
+}
+
+const DEFAULT: Theme = Theme {
+    text: Color::White,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    background: Color::Black,
+    border: Color::Gray,
+    border_focused: Color::Cyan,
+    accent: Color::LightCyan,
+    highlight: Color::Yellow,
+    positive: Color::LightGreen,
+    negative: Color::LightRed,
+    secondary: Color::LightMagenta,
+    tertiary: Color::LightBlue,
+    gauge: Color::Green,
+    linklet_complete: Color::LightGreen,
+    linklet_active: Color::Yellow,
+    linklet_pending: Color::DarkGray,
+    rarity: [
+        Color::LightCyan,
+        Color::LightBlue,
+        Color::Magenta,
+        Color::LightYellow,
+    ],
+};
+
+/// Avoids leaning on red/green alone: gains read blue, losses magenta.
+const HIGH_CONTRAST: Theme = Theme {
+    text: Color::White,
+    label: Color::White,
+    muted: Color::Gray,
+    background: Color::Black,
+    border: Color::White,
+    border_focused: Color::LightYellow,
+    accent: Color::LightCyan,
+    highlight: Color::LightYellow,
+    positive: Color::LightBlue,
+    negative: Color::LightMagenta,
+    secondary: Color::LightCyan,
+    tertiary: Color::LightBlue,
+    gauge: Color::LightBlue,
+    linklet_complete: Color::LightBlue,
+    linklet_active: Color::LightYellow,
+    linklet_pending: Color::Gray,
+    rarity: [
+        Color::White,
+        Color::LightCyan,
+        Color::LightMagenta,
+        Color::LightYellow,
+    ],
+};
+
+const MONOCHROME: Theme = Theme {
+    text: Color::White,
+    label: Color::Gray,
+    muted: Color::DarkGray,
+    background: Color::Black,
+    border: Color::Gray,
+    border_focused: Color::White,
+    accent: Color::White,
+    highlight: Color::White,
+    positive: Color::White,
+    negative: Color::White,
+    secondary: Color::Gray,
+    tertiary: Color::Gray,
+    gauge: Color::White,
+    linklet_complete: Color::White,
+    linklet_active: Color::Gray,
+    linklet_pending: Color::DarkGray,
+    rarity: [Color::Gray, Color::White, Color::White, Color::White],
+};
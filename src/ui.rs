@@ -1,23 +1,124 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::prelude::*;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Sparkline, Wrap,
 };
 use std::time::Duration;
 
+use crate::achievements::AchievementId;
 use crate::app::{
-    ActiveJob, App, LedgerEntry, LinkletStatus, PaneFocus, format_duration, format_price_delta,
-    format_relings,
+    AUTO_SELL_CAP_STEP, ActiveJob, App, CONTRACT_TTL_MAX_SECS, ChartMode, ChartScale,
+    CompletionFlash, EARLY_UNSTAKE_PENALTY, EXCHANGE_FLAT_FEE, GridGlyphs, HashpowerView,
+    JobRarity, LOAN_AUTO_REPAY_SHARE, LOAN_RATE_PER_HOUR, LOAN_STEP, LONG_MA_WINDOW, LedgerEntry,
+    LinkletStatus, ListRegion, MANIPULATION_COOLDOWN_SECS, MANIPULATION_MIN_VALUE, MaCrossover,
+    MessageCategory, MiningJob, OrderSide, Overclock, PaneFocus, PaneRects, PauseConfirm,
+    PauseMenuItem, SHORT_MA_WINDOW, STAKE_STEP, STAKING_RATE_PER_HOUR, TIER_UNLOCK_OWNED,
+    WorkStrategy, format_duration, format_eta, format_price, format_price_delta, format_relings,
 };
+use crate::daily::{DAILY_GOAL_NET_WORTH, DailyOutcome};
+use crate::keymap::Action;
+use crate::preset::Preset;
+use crate::theme::Theme;
+
+/// Narrowest terminal the stacked layout still fits, such as a split pane
+/// or a phone over SSH; the side-by-side split needs `STACK_BELOW_WIDTH`.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 24;
+/// Narrower terminals get a single column instead of the side-by-side split.
+const STACK_BELOW_WIDTH: u16 = 100;
+const POPUP_MIN_WIDTH: u16 = 36;
+
+/// How the panes are arranged for a given terminal size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LayoutPlan {
+    /// Mining and bank on the left; hashpower, ledger and ticker on the right.
+    Columns,
+    /// One column where the focused pane takes the slack and every other pane
+    /// is held to `collapsed_rows`.
+    Stacked { collapsed_rows: u16 },
+}
+
+fn layout_plan(size: Rect) -> LayoutPlan {
+    if size.width >= STACK_BELOW_WIDTH {
+        return LayoutPlan::Columns;
+    }
+    // Four panes share half the height; three rows still show a title and a line.
+    LayoutPlan::Stacked {
+        collapsed_rows: (size.height / 8).max(3),
+    }
+}
+
+/// Price samples folded into each candle of the OHLC chart.
+const CANDLE_BUCKET: usize = 4;
 
 pub fn draw(f: &mut Frame<'_>, app: &App) {
+    let size = f.size();
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        draw_too_small(f, size, app.theme.palette());
+        return;
+    }
+
+    app.pane_rects.set(PaneRects::default());
+    if let Some(pane) = app.zoom {
+        match pane {
+            PaneFocus::Mining => draw_mining(f, size, app),
+            PaneFocus::Hashpower => draw_hashpower(f, size, app),
+            PaneFocus::Bank => draw_bank(f, size, app),
+            PaneFocus::Ledger => draw_ledger(f, size, app),
+        }
+    } else {
+        match layout_plan(size) {
+            LayoutPlan::Columns => draw_panes(f, size, app),
+            LayoutPlan::Stacked { collapsed_rows } => {
+                draw_stacked_panes(f, size, app, collapsed_rows)
+            }
+        }
+    }
+
+    if app.show_stats {
+        draw_stats_overlay(f, app);
+    }
+
+    if app.show_debug {
+        draw_debug_overlay(f, app);
+    }
+
+    if app.show_contract_details && app.focus == PaneFocus::Mining {
+        draw_contract_details(f, app);
+    }
+
+    if app.goal_draft.is_some() {
+        draw_goal_editor(f, app);
+    }
+
+    if app.pending_purchase.is_some() {
+        draw_purchase_confirm(f, app);
+    }
+
+    if app.pending_manipulation.is_some() {
+        draw_manipulation_confirm(f, app);
+    }
+
+    if app.daily.as_ref().is_some_and(|daily| daily.is_finished()) {
+        draw_daily_summary(f, app);
+    }
+
+    if app.paused {
+        draw_pause_overlay(f, app);
+        if app.show_achievements {
+            draw_achievements_overlay(f, app);
+        }
+    }
+}
+
+fn draw_panes(f: &mut Frame<'_>, size: Rect, app: &App) {
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-        .split(f.size());
+        .split(size);
 
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -39,14 +140,65 @@ pub fn draw(f: &mut Frame<'_>, app: &App) {
     draw_hashpower(f, right_chunks[0], app);
     draw_ledger(f, lower_right[0], app);
     draw_ticker(f, lower_right[1], app);
+}
 
-    if app.paused {
-        draw_pause_overlay(f, app);
+fn draw_stacked_panes(f: &mut Frame<'_>, size: Rect, app: &App, collapsed_rows: u16) {
+    let panes = [
+        Some(PaneFocus::Mining),
+        Some(PaneFocus::Hashpower),
+        Some(PaneFocus::Bank),
+        Some(PaneFocus::Ledger),
+        None,
+    ];
+    let constraints: Vec<Constraint> = panes
+        .iter()
+        .map(|&pane| {
+            if pane == Some(app.focus) {
+                Constraint::Min(0)
+            } else {
+                Constraint::Length(collapsed_rows)
+            }
+        })
+        .collect();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(size);
+
+    for (&pane, &area) in panes.iter().zip(chunks.iter()) {
+        match pane {
+            Some(PaneFocus::Mining) => draw_mining(f, area, app),
+            Some(PaneFocus::Hashpower) => draw_hashpower(f, area, app),
+            Some(PaneFocus::Bank) => draw_bank(f, area, app),
+            Some(PaneFocus::Ledger) => draw_ledger(f, area, app),
+            None => draw_ticker(f, area, app),
+        }
     }
 }
 
+fn stash_rects(app: &App, update: impl FnOnce(&mut PaneRects)) {
+    let mut rects = app.pane_rects.get();
+    update(&mut rects);
+    app.pane_rects.set(rects);
+}
+
 fn draw_mining(f: &mut Frame<'_>, area: Rect, app: &App) {
-    let block = pane_block("Mining", app.focus == PaneFocus::Mining);
+    let theme = app.theme.palette();
+    stash_rects(app, |rects| rects.mining = area);
+    let mut title = if app.mining.auto_accept {
+        "Mining [auto]".to_string()
+    } else {
+        "Mining".to_string()
+    };
+    if let Some(daily) = &app.daily {
+        title.push_str(&format!(
+            " · Daily {} · {} left · goal {:.0}₵",
+            daily.date.format("%Y-%m-%d"),
+            format_duration(Duration::from_secs_f64(daily.remaining_secs())),
+            DAILY_GOAL_NET_WORTH
+        ));
+    }
+    let block = pane_block(&title, app.focus == PaneFocus::Mining, theme);
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
     let segments = Layout::default()
@@ -59,14 +211,18 @@ fn draw_mining(f: &mut Frame<'_>, area: Rect, app: &App) {
 }
 
 fn draw_active_job(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
     let block = Block::default()
-        .title("Active Link")
+        .title(format!("Active Link · {}", app.mining.strategy.label()))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(Style::default().fg(theme.muted));
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
 
-    if let Some(active) = &app.mining.active_job {
+    if let Some(flash) = &app.mining.completion_flash {
+        let lines = build_flash_lines(flash, inner.width, app.grid_glyphs, theme);
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    } else if let Some(active) = &app.mining.active_job {
         let segments = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -74,20 +230,33 @@ fn draw_active_job(f: &mut Frame<'_>, area: Rect, app: &App) {
 
         let ratio = active.completion_ratio();
         let gauge = Gauge::default()
-            .block(Block::default().title(active.job.name.as_str()))
+            .block(Block::default().title(Span::styled(
+                format!("{} · {}", active.job.name, active.job.rarity.label()),
+                rarity_style(active.job.rarity, theme),
+            )))
             .ratio(ratio)
             .gauge_style(
                 Style::default()
-                    .fg(Color::Green)
-                    .bg(Color::Black)
+                    .fg(theme.gauge)
+                    .bg(theme.background)
                     .add_modifier(Modifier::BOLD),
             )
             .label(format!("{:.0}%", ratio * 100.0));
         f.render_widget(gauge, segments[0]);
 
-        let info = build_active_job_lines(active, app.hashpower.total_power());
+        let info = build_active_job_lines(
+            active,
+            app.hashpower.effective_power(),
+            segments[1].width,
+            app.grid_glyphs,
+            app.mining.strategy,
+            theme,
+        );
         let paragraph = Paragraph::new(info).wrap(Wrap { trim: false });
         f.render_widget(paragraph, segments[1]);
+    } else if let Some(job) = app.mining.available_jobs.get(app.mining.selected_job) {
+        let lines = build_preview_lines(job, inner.width, app.grid_glyphs, theme);
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
     } else {
         let placeholder =
             Paragraph::new("No active mining contract. Select one below and press Enter.")
@@ -96,31 +265,166 @@ fn draw_active_job(f: &mut Frame<'_>, area: Rect, app: &App) {
     }
 }
 
-fn build_active_job_lines(active: &ActiveJob, power: f64) -> Vec<Line<'static>> {
+/// The just-finished board, every linklet full, alternating colour each tick.
+fn build_flash_lines(
+    flash: &CompletionFlash,
+    width: u16,
+    glyphs: GridGlyphs,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let job = &flash.job;
+    let spaced = job.cols * 2 - 1 <= usize::from(width);
+    let style = if flash.frames_left.is_multiple_of(2) {
+        Style::default()
+            .fg(theme.positive)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.linklet_complete)
+    };
+    let glyph = grid_glyph(1.0, glyphs).to_string();
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{} · link restored", job.name),
+            rarity_style(job.rarity, theme).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    for _ in 0..job.rows {
+        let mut spans = Vec::new();
+        for col in 0..job.cols {
+            spans.push(Span::styled(glyph.clone(), style));
+            if spaced && col + 1 < job.cols {
+                spans.push(Span::raw(" "));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+const COARSE_GLYPHS: &[char] = &['·', '░', '▒', '▓', '█'];
+const FINE_GLYPHS: &[char] = &['·', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Picks the glyph for a 0..=1 fill level from the chosen set.
+fn grid_glyph(level: f64, glyphs: GridGlyphs) -> char {
+    let set = match glyphs {
+        GridGlyphs::Coarse => COARSE_GLYPHS,
+        GridGlyphs::Fine => FINE_GLYPHS,
+    };
+    let index = (level.clamp(0.0, 1.0) * (set.len() - 1) as f64).round() as usize;
+    set[index.min(set.len() - 1)]
+}
+
+/// Heatmap of a listed contract's linklets, denser glyphs marking harder cells.
+fn build_preview_lines(
+    job: &MiningJob,
+    width: u16,
+    glyphs: GridGlyphs,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let hardest = job
+        .linklet_difficulties
+        .iter()
+        .copied()
+        .fold(0.0_f64, f64::max);
+    let easiest = job
+        .linklet_difficulties
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let spaced = job.cols * 2 - 1 <= usize::from(width);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Preview ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{} · {}", job.name, job.rarity.label()),
+                rarity_style(job.rarity, theme),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Linklets ", Style::default().fg(theme.label)),
+            Span::raw(format!(
+                "{}  Δ{:.1}–{:.1}",
+                job.linklet_difficulties.len(),
+                easiest,
+                hardest
+            )),
+        ]),
+        Line::from(""),
+    ];
+    for row in 0..job.rows {
+        let mut spans = Vec::new();
+        for col in 0..job.cols {
+            let Some(&difficulty) = job.linklet_difficulties.get(row * job.cols + col) else {
+                break;
+            };
+            let level = if hardest > f64::EPSILON {
+                difficulty / hardest
+            } else {
+                0.0
+            };
+            let style = if level >= 0.75 {
+                Style::default()
+                    .fg(theme.linklet_active)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            spans.push(Span::styled(grid_glyph(level, glyphs).to_string(), style));
+            if spaced && col + 1 < job.cols {
+                spans.push(Span::raw(" "));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "No active mining contract. Press Enter to accept this one.",
+        Style::default().fg(theme.muted),
+    )));
+    lines
+}
+
+fn build_active_job_lines(
+    active: &ActiveJob,
+    power: f64,
+    width: u16,
+    glyphs: GridGlyphs,
+    strategy: WorkStrategy,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
     let job = &active.job;
-    let remaining = active.remaining_work();
-    let estimate = if power > 0.01 { remaining / power } else { 0.0 };
+    // Wide boards drop the gap between glyphs rather than wrapping rows.
+    let spaced = job.cols * 2 - 1 <= usize::from(width);
 
     lines.push(Line::from(vec![
-        Span::styled("Difficulty ", Style::default().fg(Color::Gray)),
+        Span::styled("Difficulty ", Style::default().fg(theme.label)),
         Span::raw(format!("{:.1}", job.difficulty)),
         Span::raw(" | Payout "),
         Span::styled(
-            format!("{:.2} ⛓", job.payout_chain),
-            Style::default().fg(Color::LightCyan),
+            format!("{:.2} {}", job.payout_chain, job.asset.symbol()),
+            Style::default().fg(theme.accent),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("Estimate ", Style::default().fg(Color::Gray)),
-        Span::raw(format_duration(Duration::from_secs_f64(estimate))),
+        Span::styled("Estimate ", Style::default().fg(theme.label)),
+        Span::raw(format_eta(active.estimate_secs(power))),
         Span::raw(" @ "),
-        Span::styled(format_relings(power), Style::default().fg(Color::Yellow)),
+        Span::styled(format_relings(power), Style::default().fg(theme.highlight)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("On completion ", Style::default().fg(theme.label)),
+        market_nudge_span(job, theme),
+        Span::styled(
+            format!(" {} price, scaled by payout", job.asset.symbol()),
+            Style::default().fg(theme.muted),
+        ),
     ]));
     lines.push(Line::from(""));
 
-    let statuses = active.status_map();
-    const GLYPHS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+    let statuses = active.status_map(strategy);
     for row in 0..job.rows {
         let mut spans = Vec::new();
         for col in 0..job.cols {
@@ -131,18 +435,16 @@ fn build_active_job_lines(active: &ActiveJob, power: f64) -> Vec<Line<'static>>
             } else {
                 1.0 - (linklet.remaining / linklet.difficulty).clamp(0.0, 1.0)
             };
-            let glyph_index = ((progress * ((GLYPHS.len() - 1) as f64)).round() as usize)
-                .clamp(0, GLYPHS.len() - 1);
-            let glyph = GLYPHS[glyph_index];
+            let glyph = grid_glyph(progress, glyphs);
             let style = match statuses[idx] {
-                LinkletStatus::Complete => Style::default().fg(Color::LightGreen),
+                LinkletStatus::Complete => Style::default().fg(theme.linklet_complete),
                 LinkletStatus::Active => Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.linklet_active)
                     .add_modifier(Modifier::BOLD),
-                LinkletStatus::Pending => Style::default().fg(Color::DarkGray),
+                LinkletStatus::Pending => Style::default().fg(theme.linklet_pending),
             };
             spans.push(Span::styled(glyph.to_string(), style));
-            if col + 1 < job.cols {
+            if spaced && col + 1 < job.cols {
                 spans.push(Span::raw(" "));
             }
         }
@@ -153,7 +455,7 @@ fn build_active_job_lines(active: &ActiveJob, power: f64) -> Vec<Line<'static>>
     lines.push(Line::from(vec![Span::styled(
         job.lore.clone(),
         Style::default()
-            .fg(Color::Gray)
+            .fg(theme.label)
             .add_modifier(Modifier::ITALIC),
     )]));
 
@@ -161,114 +463,285 @@ fn build_active_job_lines(active: &ActiveJob, power: f64) -> Vec<Line<'static>>
 }
 
 fn draw_contracts(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
+    let mut title = format!("Contracts · sort: {}", app.mining.sort.label());
+    if app.mining.reroll_cooldown > 0.0 {
+        title.push_str(&format!(
+            " · reroll in {:.0}s",
+            app.mining.reroll_cooldown.ceil()
+        ));
+    }
     let block = Block::default()
-        .title("Contracts")
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(Style::default().fg(theme.muted));
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
 
+    let pending = app.mining.pending_slots();
     if app.mining.available_jobs.is_empty() {
-        let paragraph =
-            Paragraph::new("No contracts available. Wait for new fragments to surface.")
-                .wrap(Wrap { trim: true });
+        let text = match pending.first() {
+            Some(secs) => format!(
+                "No contracts available. Next contract in {:.0}s.",
+                secs.ceil()
+            ),
+            None => "No contracts available. Wait for new fragments to surface.".to_string(),
+        };
+        let paragraph = Paragraph::new(text).wrap(Wrap { trim: true });
         f.render_widget(paragraph, inner);
         return;
     }
 
-    let items: Vec<ListItem> = app
-        .mining
-        .available_jobs
+    let order = app.mining.display_order();
+    let power = app.hashpower.effective_power();
+    let best = app.mining.best_expected_value();
+    let mut items: Vec<ListItem> = order
         .iter()
-        .enumerate()
-        .map(|(idx, job)| {
-            let est = if app.hashpower.total_power() > 0.01 {
-                job.difficulty / app.hashpower.total_power()
-            } else {
-                0.0
-            };
+        .map(|&idx| {
+            let job = &app.mining.available_jobs[idx];
+            let est = app.mining.queued_estimate_secs(job, power);
             let content = vec![Line::from(vec![
-                Span::styled(job.name.clone(), Style::default().fg(Color::LightCyan)),
+                ttl_span(job, theme),
+                Span::styled(job.name.clone(), rarity_style(job.rarity, theme)),
                 Span::raw(format!("  {}x{}", job.rows, job.cols)),
                 Span::raw("  Δ"),
                 Span::raw(format!("{:.1}", job.difficulty)),
-                Span::raw("  ⛓ "),
+                Span::raw(format!("  {} ", job.asset.symbol())),
                 Span::raw(format!("{:.2}", job.payout_chain)),
                 Span::raw("  η"),
-                Span::raw(format_duration(Duration::from_secs_f64(est))),
+                Span::raw(format_eta(est)),
+                Span::raw("  "),
+                market_nudge_span(job, theme),
+                Span::styled(
+                    format!(
+                        "  {:.2}₵/s",
+                        job.expected_value_per_work(&app.mining.quotes)
+                            * power
+                            * app.legacy_multiplier
+                    ),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    if best == Some(idx) { " ★" } else { "" },
+                    Style::default().fg(theme.highlight),
+                ),
             ])];
             let mut item = ListItem::new(content);
             if idx == app.mining.selected_job {
-                item = item.style(Style::default().fg(Color::Yellow));
+                item = item.style(Style::default().fg(theme.highlight));
             }
             item
         })
         .collect();
+    items.extend(pending.iter().map(|secs| {
+        ListItem::new(Line::from(Span::styled(
+            format!("  next contract in {:.0}s", secs.ceil()),
+            Style::default().fg(theme.muted),
+        )))
+    }));
 
     let list = List::new(items).highlight_style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD),
     );
     let mut state = ListState::default();
-    state.select(Some(app.mining.selected_job));
+    state.select(Some(app.mining.display_position(&order)));
     f.render_stateful_widget(list, inner, &mut state);
+    stash_rects(app, |rects| {
+        rects.contracts = ListRegion {
+            area: inner,
+            offset: state.offset(),
+        }
+    });
+}
+
+/// Four-cell gauge of the time a listed contract has left before it expires.
+fn ttl_span(job: &MiningJob, theme: &Theme) -> Span<'static> {
+    const CELLS: usize = 4;
+    let fraction = (job.expires_in / CONTRACT_TTL_MAX_SECS).clamp(0.0, 1.0);
+    let filled = ((fraction * CELLS as f64).ceil() as usize).min(CELLS);
+    let color = if filled <= 1 {
+        theme.negative
+    } else {
+        theme.muted
+    };
+    Span::styled(
+        format!("{}{} ", "▮".repeat(filled), "▯".repeat(CELLS - filled)),
+        Style::default().fg(color),
+    )
+}
+
+/// "↑ market 0.12₵" style marker for the price move a contract will cause.
+fn market_nudge_span(job: &MiningJob, theme: &Theme) -> Span<'static> {
+    let nudge = job.projected_nudge();
+    if nudge.abs() < 0.005 {
+        return Span::styled("→ market", Style::default().fg(theme.muted));
+    }
+    let (arrow, color) = if nudge > 0.0 {
+        ("↑", theme.positive)
+    } else {
+        ("↓", theme.negative)
+    };
+    Span::styled(
+        format!("{} market {:.2}₵", arrow, nudge.abs()),
+        Style::default().fg(color),
+    )
+}
+
+fn rarity_style(rarity: JobRarity, theme: &Theme) -> Style {
+    let style = Style::default().fg(theme.rarity[rarity as usize]);
+    match rarity {
+        JobRarity::Common | JobRarity::Rare => style,
+        JobRarity::Epic | JobRarity::Mythic => style.add_modifier(Modifier::BOLD),
+    }
 }
 
 fn draw_hashpower(f: &mut Frame<'_>, area: Rect, app: &App) {
-    let block = pane_block("Hashpower", app.focus == PaneFocus::Hashpower);
+    let theme = app.theme.palette();
+    stash_rects(app, |rects| rects.hashpower = area);
+    let title = match app.hashpower.view {
+        HashpowerView::Tiers => "Hashpower",
+        HashpowerView::Research => "Hashpower · Research",
+    };
+    let block = pane_block(title, app.focus == PaneFocus::Hashpower, theme);
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
 
-    let total_power = app.hashpower.total_power();
-    let header = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Total ", Style::default().fg(Color::Gray)),
-        Span::styled(
-            format_relings(total_power),
-            Style::default().fg(Color::LightGreen),
+    let total_power = app.hashpower.effective_power();
+    let overclock = match app.hashpower.overclock {
+        Overclock::Active { remaining_secs } => Span::styled(
+            format!("OVERCLOCKED {:.0}s", remaining_secs.max(0.0)),
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
         ),
-        Span::raw("  |  Credits "),
-        Span::styled(
-            format!("{:.2}₵", app.bank.credits_balance),
-            Style::default().fg(Color::LightCyan),
+        Overclock::Cooldown { remaining_secs } => Span::styled(
+            format!("Cooling down {:.0}s", remaining_secs.max(0.0)),
+            Style::default().fg(theme.muted),
         ),
-    ])]);
+        Overclock::Idle => Span::styled(
+            format!("[{}] overclock", app.keymap.label(Action::Overclock)),
+            Style::default().fg(theme.muted),
+        ),
+    };
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("Total ", Style::default().fg(theme.label)),
+            Span::styled(
+                format_relings(total_power),
+                Style::default().fg(theme.positive),
+            ),
+            Span::raw("  |  Credits "),
+            Span::styled(
+                format!("{:.2}₵", app.bank.credits_balance),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            overclock,
+            Span::styled(
+                format!(
+                    "  [{}] {}",
+                    app.keymap.label(Action::ToggleResearch),
+                    match app.hashpower.view {
+                        HashpowerView::Tiers => "research",
+                        HashpowerView::Research => "rigs",
+                    }
+                ),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                format!(
+                    "  [{}] ×10  [{}] max",
+                    app.keymap.label(Action::PurchaseTen),
+                    app.keymap.label(Action::PurchaseMax)
+                ),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        build_rate_line(app, total_power, theme),
+    ]);
     let segments = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
         .split(inner);
     f.render_widget(header, segments[0]);
 
+    if app.hashpower.view == HashpowerView::Research {
+        draw_research(f, segments[1], app);
+        return;
+    }
+    let segments = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(segments[1]);
+    f.render_widget(
+        Paragraph::new(build_power_share_bar(app, segments[0].width, theme)),
+        segments[0],
+    );
+
+    let best = app.hashpower.most_efficient_tier();
     let items: Vec<ListItem> = app
         .hashpower
         .tiers
         .iter()
-        .map(|tier| {
+        .enumerate()
+        .map(|(idx, tier)| {
+            if !app.hashpower.is_unlocked(idx) {
+                let previous = &app.hashpower.tiers[idx - 1];
+                let locked = Line::from(vec![
+                    Span::styled(" —  ", Style::default().fg(theme.muted)),
+                    Span::styled(
+                        format!("{:<14}", tier.name),
+                        Style::default().fg(theme.muted),
+                    ),
+                    Span::styled(
+                        format!(
+                            " Unlocks at {} {} ({}/{})",
+                            TIER_UNLOCK_OWNED, previous.name, previous.owned, TIER_UNLOCK_OWNED
+                        ),
+                        Style::default().fg(theme.muted),
+                    ),
+                ]);
+                return ListItem::new(vec![locked]);
+            }
             let owned_style = if tier.owned > 0 {
-                Style::default().fg(Color::LightGreen)
+                Style::default().fg(theme.positive)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
             let total_power = format_relings(tier.total_power());
-            let unit_power = format_relings(tier.power);
+            let unit_power = format_relings(tier.unit_power());
             let content = Line::from(vec![
                 Span::styled(format!("{:>2}×", tier.owned), owned_style),
                 Span::raw(" "),
                 Span::styled(
                     format!("{:<14}", tier.name),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme.text),
                 ),
                 Span::styled(
                     format!(" {:>10}", total_power),
-                    Style::default().fg(Color::LightGreen),
+                    Style::default().fg(theme.positive),
+                ),
+                Span::styled(
+                    format!(" {:>3.0}%", app.hashpower.power_share(idx) * 100.0),
+                    Style::default().fg(share_color(idx, theme)),
                 ),
-                Span::raw(" total"),
                 Span::raw("  +"),
-                Span::styled(unit_power, Style::default().fg(Color::Gray)),
-                Span::raw("/ea  next:"),
+                Span::styled(unit_power, Style::default().fg(theme.label)),
+                Span::raw(" for"),
                 Span::styled(
                     format!(" {:.2}₵", tier.cost_for_next()),
-                    Style::default().fg(Color::LightCyan),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(
+                    format!("  {:.2}/k₵", tier.power_per_credit() * 1000.0),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    if best == Some(idx) { " ★" } else { "" },
+                    Style::default().fg(theme.highlight),
                 ),
             ]);
             ListItem::new(vec![content])
@@ -279,162 +752,726 @@ fn draw_hashpower(f: &mut Frame<'_>, area: Rect, app: &App) {
         .block(Block::default().borders(Borders::NONE))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         );
     let mut state = ListState::default();
     state.select(Some(app.hashpower.selected));
     f.render_stateful_widget(list, segments[1], &mut state);
+    stash_rects(app, |rects| {
+        rects.tiers = ListRegion {
+            area: segments[1],
+            offset: state.offset(),
+        }
+    });
+}
+
+/// Estimated chain and credits per second at `power`, judged from the listed contracts.
+fn build_rate_line(app: &App, power: f64, theme: &Theme) -> Line<'static> {
+    let label = Span::styled("Est. ", Style::default().fg(theme.label));
+    let Some(rates) = app.mining.estimated_rates(power, app.legacy_multiplier) else {
+        return Line::from(vec![
+            label,
+            Span::styled("— (no rigs or contracts)", Style::default().fg(theme.muted)),
+        ]);
+    };
+    let chain = match rates.chain_per_sec {
+        Some(chain) => format!("~{:.3}⛓/s", chain),
+        None => "—⛓/s".to_string(),
+    };
+    Line::from(vec![
+        label,
+        Span::styled(chain, Style::default().fg(theme.positive)),
+        Span::raw("  "),
+        Span::styled(
+            format!("~{:.2}₵/s", rates.credits_per_sec),
+            Style::default().fg(theme.accent),
+        ),
+        Span::styled(
+            "  varies by contract taken",
+            Style::default().fg(theme.muted),
+        ),
+    ])
+}
+
+/// Colour of a tier's segment in the power share bar, matched by its % column.
+fn share_color(idx: usize, theme: &Theme) -> Color {
+    let palette = [
+        theme.positive,
+        theme.accent,
+        theme.highlight,
+        theme.secondary,
+        theme.tertiary,
+        theme.rarity[1],
+        theme.rarity[2],
+        theme.rarity[3],
+    ];
+    palette[idx % palette.len()]
+}
+
+/// One row split into a segment per owned tier, sized by its share of base power.
+fn build_power_share_bar(app: &App, width: u16, theme: &Theme) -> Line<'static> {
+    let width = usize::from(width);
+    let mut spans = Vec::new();
+    let mut drawn = 0;
+    let mut cumulative = 0.0;
+    for idx in 0..app.hashpower.tiers.len() {
+        let share = app.hashpower.power_share(idx);
+        if share <= 0.0 {
+            continue;
+        }
+        // Round the running total so segment widths always add up to the row.
+        cumulative += share;
+        let end = ((cumulative * width as f64).round() as usize).min(width);
+        if end > drawn {
+            spans.push(Span::styled(
+                "█".repeat(end - drawn),
+                Style::default().fg(share_color(idx, theme)),
+            ));
+            drawn = end;
+        }
+    }
+    if drawn < width {
+        spans.push(Span::styled(
+            "░".repeat(width - drawn),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn draw_research(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
+    let items: Vec<ListItem> = app
+        .hashpower
+        .research
+        .iter()
+        .map(|upgrade| {
+            let (marker, marker_style) = if upgrade.purchased {
+                ("✓", Style::default().fg(theme.positive))
+            } else {
+                ("·", Style::default().fg(theme.muted))
+            };
+            let mut cost = format!("{:.2}₵", upgrade.credit_cost);
+            if upgrade.chain_cost > 0.0 {
+                cost.push_str(&format!(" + {:.0} ⛓", upgrade.chain_cost));
+            }
+            let content = Line::from(vec![
+                Span::styled(marker, marker_style),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<32}", upgrade.name),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    format!(
+                        " +{:.0}% {}",
+                        upgrade.power_bonus * 100.0,
+                        app.hashpower.tiers[upgrade.tier].name
+                    ),
+                    Style::default().fg(theme.positive),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    if upgrade.purchased {
+                        "researched".to_string()
+                    } else {
+                        cost
+                    },
+                    Style::default().fg(theme.accent),
+                ),
+            ]);
+            ListItem::new(vec![content])
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(theme.highlight)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut state = ListState::default();
+    state.select(Some(app.hashpower.research_selected));
+    f.render_stateful_widget(list, area, &mut state);
+    stash_rects(app, |rects| {
+        rects.tiers = ListRegion {
+            area,
+            offset: state.offset(),
+        }
+    });
 }
 
 fn draw_bank(f: &mut Frame<'_>, area: Rect, app: &App) {
-    let block = pane_block("Bank & Exchange", app.focus == PaneFocus::Bank);
+    let theme = app.theme.palette();
+    stash_rects(app, |rects| rects.bank = area);
+    let block = pane_block("Bank & Exchange", app.focus == PaneFocus::Bank, theme);
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
 
-    let delta = format_price_delta(app.ticker.last_delta);
-    let lines = vec![
+    let asset = app.bank_asset;
+    let ticker = app.ticker_for(asset);
+    let delta = format_price_delta(ticker.last_delta);
+    let mut lines = vec![
         Line::from(vec![
-            Span::styled("Chain ", Style::default().fg(Color::Gray)),
+            Span::styled("Chain ", Style::default().fg(theme.label)),
             Span::styled(
                 format!("{:.2} ⛓", app.bank.chain_balance),
-                Style::default().fg(Color::LightCyan),
+                Style::default().fg(theme.accent),
+            ),
+            Span::raw("  |  Shards "),
+            Span::styled(
+                format!("{:.2} ◆", app.bank.shard_balance),
+                Style::default().fg(theme.secondary),
             ),
             Span::raw("  |  Credits "),
             Span::styled(
                 format!("{:.2}₵", app.bank.credits_balance),
-                Style::default().fg(Color::LightGreen),
+                Style::default().fg(theme.positive),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Market ", Style::default().fg(Color::Gray)),
             Span::styled(
-                format!("{:.2}₵", app.ticker.price),
-                Style::default().fg(Color::Yellow),
+                format!("Trading {} ", asset.label()),
+                Style::default().fg(theme.label),
+            ),
+            Span::styled(
+                format!("{}₵", format_price(ticker.price)),
+                Style::default().fg(theme.highlight),
             ),
             Span::raw("  ("),
-            Span::styled(delta, Style::default().fg(Color::Gray)),
+            Span::styled(delta, Style::default().fg(theme.label)),
             Span::raw(")"),
+            Span::styled(
+                format!("  [{}] switch asset", app.keymap.label(Action::SwitchAsset)),
+                Style::default().fg(theme.muted),
+            ),
         ]),
         Line::from(""),
-        Line::from("← sell 1 chain  |  → buy 1 chain  |  [B] buy 5  |  [M] sell 5"),
-        Line::from("Spread 1%: sells settle at 0.99×, buys at 1.01× market."),
+        Line::from(format!(
+            "{} sell 1 {}  |  {} buy 1 {}  |  [{}] buy 5  |  [{}] sell 5",
+            app.keymap.label(Action::SellChain),
+            asset.symbol(),
+            app.keymap.label(Action::BuyChain),
+            asset.symbol(),
+            app.keymap.label(Action::BuyBulk),
+            app.keymap.label(Action::SellBulk)
+        )),
+        Line::from(format!(
+            "Spread {:.1}%: sells settle at {:.3}×, buys at {:.3}× market, plus {:.2}₵ per trade.",
+            app.bank.spread * 100.0,
+            app.bank.sell_multiplier(),
+            app.bank.buy_multiplier(),
+            EXCHANGE_FLAT_FEE
+        )),
+        Line::from(vec![
+            Span::styled("Savings ", Style::default().fg(theme.label)),
+            if app.config.savings_interest {
+                Span::styled(
+                    format!("{:.1}%/h", app.bank.savings_rate_per_hour * 100.0),
+                    Style::default().fg(theme.positive),
+                )
+            } else {
+                Span::styled("off", Style::default().fg(theme.muted))
+            },
+            Span::raw(" on idle credits  |  earned "),
+            Span::styled(
+                format!("{:.2}₵", app.bank.interest_earned),
+                Style::default().fg(theme.positive),
+            ),
+            Span::raw("  |  fees paid "),
+            Span::styled(
+                format!("{:.2}₵", app.bank.total_fees_paid),
+                Style::default().fg(theme.negative),
+            ),
+        ]),
+        build_auto_sell_line(app, theme),
+        build_staking_line(app, theme),
+        build_loan_line(app, theme),
+        build_manipulation_line(app, theme),
     ];
+    lines.extend(build_limit_order_lines(app));
 
     let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
     f.render_widget(paragraph, inner);
 }
 
-fn draw_ledger(f: &mut Frame<'_>, area: Rect, app: &App) {
-    let block = pane_block("Ledger", app.focus == PaneFocus::Ledger);
-    f.render_widget(block.clone(), area);
-    let inner = block.inner(area);
+fn build_auto_sell_line(app: &App, theme: &Theme) -> Line<'static> {
+    let (state, style) = if app.bank.auto_sell_enabled {
+        ("on", Style::default().fg(theme.positive))
+    } else {
+        ("off", Style::default().fg(theme.muted))
+    };
+    Line::from(vec![
+        Span::styled("Auto-sell ", Style::default().fg(theme.label)),
+        Span::styled(state, style),
+        Span::raw(format!(
+            "  chain above {:.0} ⛓ sells at market",
+            app.bank.auto_sell_cap
+        )),
+        Span::styled(
+            format!(
+                "  [{}] toggle  {} {} cap ±{:.0}",
+                app.keymap.label(Action::ToggleAutoSell),
+                app.keymap.label(Action::LowerAutoSellCap),
+                app.keymap.label(Action::RaiseAutoSellCap),
+                AUTO_SELL_CAP_STEP
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ])
+}
 
-    if app.ledger.entries.is_empty() {
-        let paragraph = Paragraph::new("Ledger empty. Restore links to uncover history.")
-            .wrap(Wrap { trim: true });
-        f.render_widget(paragraph, inner);
-        return;
+fn build_staking_line(app: &App, theme: &Theme) -> Line<'static> {
+    let bank = &app.bank;
+    let mut spans = vec![
+        Span::styled("Staked ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{:.2} ⛓", bank.staked),
+            Style::default().fg(theme.accent),
+        ),
+        Span::raw(format!(
+            " at {:.0}%/h  |  earned {:.2} ⛓",
+            STAKING_RATE_PER_HOUR * 100.0,
+            bank.staking_earned
+        )),
+    ];
+    if bank.stake_lock > 0.0 {
+        spans.push(Span::styled(
+            format!(
+                "  |  locked {}s ({:.0}% to exit early)",
+                bank.stake_lock.ceil(),
+                EARLY_UNSTAKE_PENALTY * 100.0
+            ),
+            Style::default().fg(theme.negative),
+        ));
     }
-
-    let visible_height = inner.height.saturating_sub(2) as usize;
-    let start = app.ledger.scroll.min(app.ledger.entries.len());
-    let end = (start + visible_height).min(app.ledger.entries.len());
-    let items: Vec<ListItem> = app.ledger.entries[start..end]
-        .iter()
-        .map(build_ledger_item)
+    spans.push(Span::styled(
+        format!(
+            "  [{}] stake {:.0}  [{}] unstake",
+            app.keymap.label(Action::Stake),
+            STAKE_STEP,
+            app.keymap.label(Action::Unstake)
+        ),
+        Style::default().fg(theme.muted),
+    ));
+    Line::from(spans)
+}
+
+fn build_loan_line(app: &App, theme: &Theme) -> Line<'static> {
+    let keys = Span::styled(
+        format!(
+            "  [{}] borrow {:.0}₵  [{}] repay",
+            app.keymap.label(Action::Borrow),
+            LOAN_STEP,
+            app.keymap.label(Action::Repay)
+        ),
+        Style::default().fg(theme.muted),
+    );
+    if app.bank.debt <= 0.0 {
+        return Line::from(vec![
+            Span::styled("Loan ", Style::default().fg(theme.label)),
+            Span::raw(format!(
+                "up to {:.0}₵ at {:.0}%/h",
+                app.loan_limit(),
+                LOAN_RATE_PER_HOUR * 100.0
+            )),
+            keys,
+        ]);
+    }
+    Line::from(vec![
+        Span::styled("Debt ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{:.2}₵", app.bank.debt),
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            " of {:.0}₵ at {:.0}%/h  |  interest accrued {:.2}₵  |  {:.0}% of sales repay",
+            app.loan_limit(),
+            LOAN_RATE_PER_HOUR * 100.0,
+            app.bank.loan_interest_paid,
+            LOAN_AUTO_REPAY_SHARE * 100.0
+        )),
+        keys,
+    ])
+}
+
+fn build_limit_order_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = app.theme.palette();
+    let mut lines = Vec::new();
+    if let Some(draft) = &app.alert_draft {
+        lines.push(Line::from(vec![
+            Span::styled("New alert ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!(
+                    "{} {:.2}₵{}",
+                    draft.direction.label(),
+                    draft.price,
+                    if draft.rearm {
+                        " (re-arming)"
+                    } else {
+                        " (one-shot)"
+                    }
+                ),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(format!(
+            "{}{} price  [{}] re-arm  Enter set  Esc cancel",
+            app.keymap.label(Action::DraftLower),
+            app.keymap.label(Action::DraftRaise),
+            app.keymap.label(Action::ToggleRearm)
+        )));
+        return lines;
+    }
+    if let Some(draft) = &app.order_draft {
+        lines.push(Line::from(vec![
+            Span::styled("New limit ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!(
+                    "{} {:.1} ⛓ @ {:.2}₵",
+                    draft.side.label(),
+                    draft.amount,
+                    draft.trigger_price
+                ),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(Line::from(format!(
+            "{}{} price  {}{} amount  [{}] side  Enter place  Esc cancel",
+            app.keymap.label(Action::DraftLower),
+            app.keymap.label(Action::DraftRaise),
+            app.keymap.label(Action::MoveUp),
+            app.keymap.label(Action::MoveDown),
+            app.keymap.label(Action::ToggleOrderSide)
+        )));
+        return lines;
+    }
+
+    let mut spans = vec![Span::styled("Orders ", Style::default().fg(theme.label))];
+    if app.bank.limit_orders.is_empty() {
+        spans.push(Span::styled("none", Style::default().fg(theme.muted)));
+    } else {
+        for (idx, order) in app.bank.limit_orders.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw(", "));
+            }
+            spans.push(Span::styled(
+                format!(
+                    "{} {:.1}@{:.2}₵",
+                    order.side.label(),
+                    order.amount,
+                    order.trigger_price
+                ),
+                Style::default().fg(theme.secondary),
+            ));
+        }
+    }
+    spans.push(Span::raw(format!(
+        "  |  [{}] new limit  [{}] cancel last",
+        app.keymap.label(Action::NewLimitOrder),
+        app.keymap.label(Action::CancelLimitOrder)
+    )));
+    lines.push(Line::from(spans));
+
+    let mut spans = vec![Span::styled("Alerts ", Style::default().fg(theme.label))];
+    if app.ticker.alerts.is_empty() {
+        spans.push(Span::styled("none", Style::default().fg(theme.muted)));
+    } else {
+        for (idx, alert) in app.ticker.alerts.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw(", "));
+            }
+            let marker = if alert.rearm { "↻" } else { "" };
+            spans.push(Span::styled(
+                format!("{} {:.2}₵{}", alert.direction.label(), alert.price, marker),
+                Style::default().fg(theme.negative),
+            ));
+        }
+    }
+    spans.push(Span::raw(format!(
+        "  |  [{}] new alert  [{}] clear",
+        app.keymap.label(Action::NewAlert),
+        app.keymap.label(Action::ClearAlerts)
+    )));
+    lines.push(Line::from(spans));
+    lines
+}
+
+fn draw_ledger(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
+    stash_rects(app, |rects| rects.ledger = area);
+    let entries = app.ledger.visible_entries();
+    let title = if app.ledger.filter_editing || !app.ledger.filter.is_empty() {
+        let cursor = if app.ledger.filter_editing { "▏" } else { "" };
+        format!(
+            "Ledger · /{}{} ({} matches)",
+            app.ledger.filter,
+            cursor,
+            entries.len()
+        )
+    } else {
+        "Ledger".to_string()
+    };
+    let block = pane_block(&title, app.focus == PaneFocus::Ledger, theme);
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    if app.ledger.entries.is_empty() {
+        let paragraph = Paragraph::new("Ledger empty. Restore links to uncover history.")
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, inner);
+        return;
+    }
+    if entries.is_empty() {
+        let paragraph = Paragraph::new("No entries match. Esc clears the filter.")
+            .style(Style::default().fg(theme.muted))
+            .wrap(Wrap { trim: true });
+        f.render_widget(paragraph, inner);
+        return;
+    }
+
+    let visible_height = inner.height.saturating_sub(2) as usize;
+    let start = app.ledger.scroll.min(entries.len());
+    let end = (start + visible_height).min(entries.len());
+    let items: Vec<ListItem> = entries[start..end]
+        .iter()
+        .map(|entry| build_ledger_item(entry, theme))
         .collect();
 
     let list = List::new(items)
         .block(Block::default().borders(Borders::NONE))
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .highlight_style(Style::default().fg(theme.highlight));
     f.render_widget(list, inner);
+    stash_rects(app, |rects| {
+        rects.ledger_rows = ListRegion {
+            area: Rect {
+                height: (end - start) as u16,
+                ..inner
+            },
+            offset: start,
+        };
+        rects.ledger_page = visible_height;
+    });
+
+    if inner.height > 1 {
+        let summary_area = Rect {
+            y: inner.bottom() - 1,
+            height: 1,
+            ..inner
+        };
+        f.render_widget(
+            Paragraph::new(build_ledger_summary_line(app, theme)),
+            summary_area,
+        );
+    }
 }
 
-fn build_ledger_item(entry: &LedgerEntry) -> ListItem<'static> {
+fn build_ledger_summary_line(app: &App, theme: &Theme) -> Line<'static> {
+    let summary = app.ledger.summary();
+    Line::from(vec![
+        Span::styled("Σ ", Style::default().fg(theme.label)),
+        Span::styled(
+            format!("{} links", summary.entries),
+            Style::default().fg(theme.accent),
+        ),
+        Span::raw("  ⛓ "),
+        Span::styled(
+            format!("{:.2}", summary.total_payout),
+            Style::default().fg(theme.text),
+        ),
+        Span::raw("  ◆ "),
+        Span::styled(
+            format!("{:.2}", summary.total_shards),
+            Style::default().fg(theme.text),
+        ),
+        Span::raw("  ≈"),
+        Span::styled(
+            format!("{:.2}₵", summary.total_credits),
+            Style::default().fg(theme.positive),
+        ),
+        Span::raw("  avg Δ"),
+        Span::styled(
+            format!("{:.1}", summary.average_difficulty),
+            Style::default().fg(theme.highlight),
+        ),
+        Span::raw("  impact "),
+        Span::styled(
+            format!("{:+.2}₵", summary.total_market_impact),
+            Style::default().fg(theme.label),
+        ),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn build_ledger_item(entry: &LedgerEntry, theme: &Theme) -> ListItem<'static> {
     let timestamp = entry.finished_local().format("%H:%M:%S");
     let mut market_note = String::new();
     if entry.market_impact.abs() > f64::EPSILON {
         market_note = format!(" Δ{:.2}₵", entry.market_impact);
     }
     let line = Line::from(vec![
-        Span::styled(timestamp.to_string(), Style::default().fg(Color::Gray)),
+        Span::styled(timestamp.to_string(), Style::default().fg(theme.label)),
         Span::raw("  "),
-        Span::styled(entry.id.clone(), Style::default().fg(Color::LightCyan)),
+        Span::styled(entry.id.clone(), Style::default().fg(theme.accent)),
         Span::raw("  "),
-        Span::styled(entry.name.clone(), Style::default().fg(Color::White)),
-        Span::raw("  ⛓ "),
+        Span::styled(entry.name.clone(), Style::default().fg(theme.text)),
+        Span::raw(format!("  {} ", entry.asset.symbol())),
         Span::styled(
             format!("{:.2}", entry.payout_chain),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
         Span::raw("  ≈"),
         Span::styled(
             format!("{:.2}₵", entry.credits_at_completion),
-            Style::default().fg(Color::LightGreen),
+            Style::default().fg(theme.positive),
         ),
         Span::raw("  Δ"),
         Span::styled(
             format!("{:.1}", entry.difficulty),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         ),
         Span::raw("  τ"),
         Span::styled(
             format_duration(entry.duration),
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.label),
         ),
         Span::raw(market_note),
     ]);
     ListItem::new(vec![line])
 }
 
+/// Progress toward the "mine until" goal, or the key to set one.
+fn goal_span(app: &App, theme: &Theme) -> Span<'static> {
+    let Some(goal) = &app.goal else {
+        return Span::styled(
+            format!("[{}] set goal", app.keymap.label(Action::SetGoal)),
+            Style::default().fg(theme.muted),
+        );
+    };
+    let current = app.goal_value(goal.metric);
+    Span::styled(
+        format!(
+            "Goal {:.0}/{:.0} {} {:.0}%",
+            current.max(0.0),
+            goal.target,
+            goal.metric.unit(),
+            goal.progress(current) * 100.0
+        ),
+        Style::default().fg(theme.accent),
+    )
+}
+
+/// Arrow and theme colour for a price move; `None` while the price is flat.
+fn price_trend(delta: f64, theme: &Theme) -> Option<(&'static str, Color)> {
+    if delta.abs() < 0.00005 {
+        None
+    } else if delta > 0.0 {
+        Some(("↑", theme.positive))
+    } else {
+        Some(("↓", theme.negative))
+    }
+}
+
 fn draw_ticker(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
+    let title = match app.ticker.chart {
+        ChartMode::Line => format!("Ticker · scale: {}", app.ticker.chart_scale.label()),
+        ChartMode::Candles => "Ticker".to_string(),
+    };
     let block = Block::default()
-        .title("Ticker")
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Gray));
+        .border_style(Style::default().fg(theme.border));
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Percentage(50),
             Constraint::Percentage(50),
         ])
         .split(inner);
 
+    let chain_trend = price_trend(app.ticker.last_delta, theme);
+    let shard_trend = price_trend(app.shard_ticker.last_delta, theme);
     let mut spans = Vec::new();
     spans.push(Span::styled(
-        format!("Chain {:.2}₵", app.ticker.price),
-        Style::default().fg(Color::Yellow),
+        format!("Chain {}₵", format_price(app.ticker.price)),
+        Style::default().fg(chain_trend.map_or(theme.highlight, |(_, color)| color)),
     ));
     spans.push(Span::raw("  |  "));
     spans.push(Span::styled(
-        format!("Δ {}₵", format_price_delta(app.ticker.last_delta)),
-        Style::default().fg(Color::Gray),
+        format!(
+            "{} Δ {}₵",
+            chain_trend.map_or("→", |(arrow, _)| arrow),
+            format_price_delta(app.ticker.last_delta)
+        ),
+        Style::default().fg(chain_trend.map_or(theme.label, |(_, color)| color)),
     ));
     spans.push(Span::raw("  |  "));
     spans.push(Span::styled(
         format!("Credits {:.2}₵", app.bank.credits_balance),
-        Style::default().fg(Color::LightGreen),
+        Style::default().fg(theme.positive),
+    ));
+    spans.push(Span::raw("  |  "));
+    spans.push(Span::styled(
+        format!("Shards {}₵", format_price(app.shard_ticker.price)),
+        Style::default().fg(shard_trend.map_or(theme.secondary, |(_, color)| color)),
+    ));
+    spans.push(Span::raw(" "));
+    spans.push(Span::styled(
+        format!(
+            "({} {}₵)",
+            shard_trend.map_or("→", |(arrow, _)| arrow),
+            format_price_delta(app.shard_ticker.last_delta)
+        ),
+        Style::default().fg(shard_trend.map_or(theme.label, |(_, color)| color)),
     ));
     spans.push(Span::raw("  |  "));
     spans.push(Span::styled(
         format!("Holdings {:.2}", app.bank.chain_balance),
-        Style::default().fg(Color::LightCyan),
+        Style::default().fg(theme.accent),
     ));
     spans.push(Span::raw("  |  "));
     spans.push(Span::styled(
         format!("Next {:.1}s", app.ticker.seconds_until_update()),
-        Style::default().fg(Color::LightMagenta),
+        Style::default().fg(theme.secondary),
     ));
+    spans.push(Span::raw("  |  "));
+    spans.push(goal_span(app, theme));
+    if app.preset != Preset::Normal {
+        spans.push(Span::raw("  |  "));
+        spans.push(Span::styled(
+            app.preset.label(),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    if let Some(seed) = app.seed {
+        spans.push(Span::raw("  |  "));
+        spans.push(Span::styled(
+            format!("Seed {}", seed),
+            Style::default().fg(theme.muted),
+        ));
+    }
 
     let header = Paragraph::new(Line::from(spans)).alignment(Alignment::Left);
     f.render_widget(header, layout[0]);
 
-    if layout[1].height > 0 && layout[1].width > 0 {
-        let width = layout[1].width as usize;
+    let indicators = Paragraph::new(build_indicator_line(app)).alignment(Alignment::Left);
+    f.render_widget(indicators, layout[1]);
+
+    if layout[2].height > 0 && layout[2].width > 0 && app.ticker.chart == ChartMode::Candles {
+        draw_candles(f, layout[2], app);
+    } else if layout[2].height > 0 && layout[2].width > 0 {
+        let width = layout[2].width as usize;
         let mut history: Vec<f64> = app
             .ticker
             .history
@@ -445,45 +1482,809 @@ fn draw_ticker(f: &mut Frame<'_>, area: Rect, app: &App) {
             .collect();
         if !history.is_empty() {
             history.reverse();
-            let min = history
-                .iter()
-                .fold(f64::INFINITY, |acc, value| acc.min(*value));
-            let max = history
-                .iter()
-                .fold(f64::NEG_INFINITY, |acc, value| acc.max(*value));
+            let (min, max) = match app.ticker.chart_scale {
+                ChartScale::Auto => (
+                    history
+                        .iter()
+                        .fold(f64::INFINITY, |acc, value| acc.min(*value)),
+                    history
+                        .iter()
+                        .fold(f64::NEG_INFINITY, |acc, value| acc.max(*value)),
+                ),
+                ChartScale::Session => (app.ticker.session_low, app.ticker.session_high),
+            };
             let range = (max - min).max(0.01);
+            let scale = |value: f64| ((value - min) / range).clamp(0.0, 1.0);
             let data: Vec<u64> = history
                 .into_iter()
-                .map(|value| (((value - min) / range) * 100.0).round().clamp(0.0, 100.0) as u64)
+                .map(|value| (scale(value) * 100.0).round() as u64)
                 .collect();
             let sparkline = Sparkline::default()
                 .data(&data)
-                .style(Style::default().fg(Color::LightGreen));
-            f.render_widget(sparkline, layout[1]);
+                .max(100)
+                .style(Style::default().fg(theme.positive));
+            f.render_widget(sparkline, layout[2]);
+            draw_reference_line(f, layout[2], scale(app.ticker.price), theme);
         } else {
             let placeholder = Paragraph::new("Market data stabilising...")
-                .style(Style::default().fg(Color::DarkGray));
-            f.render_widget(placeholder, layout[1]);
+                .style(Style::default().fg(theme.muted));
+            f.render_widget(placeholder, layout[2]);
         }
     }
 
-    if layout[2].height > 0 {
+    if layout[3].height > 0 {
         let mut message_lines: Vec<Line> = Vec::new();
         for msg in app.messages.iter() {
-            message_lines.push(Line::from(Span::raw(msg.clone())));
+            let style = if msg.text.starts_with("ALERT:") || msg.text.starts_with("MARKET:") {
+                Style::default()
+                    .fg(theme.negative)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                match msg.category {
+                    MessageCategory::Info => Style::default(),
+                    MessageCategory::Trade => Style::default().fg(theme.accent),
+                    MessageCategory::Mining => Style::default().fg(theme.positive),
+                    MessageCategory::Error => Style::default().fg(theme.negative),
+                }
+            };
+            message_lines.push(Line::from(Span::styled(msg.display(), style)));
         }
         if message_lines.is_empty() {
             message_lines.push(Line::from(Span::styled(
                 "Awaiting signal...",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )));
         }
         let feed = Paragraph::new(message_lines).wrap(Wrap { trim: true });
-        f.render_widget(feed, layout[2]);
+        f.render_widget(feed, layout[3]);
+    }
+}
+
+/// Marks the current price level across the blank cells above the sparkline bars.
+fn draw_reference_line(f: &mut Frame<'_>, area: Rect, level: f64, theme: &Theme) {
+    let offset = ((1.0 - level) * f64::from(area.height - 1)).round() as u16;
+    let y = area.y + offset.min(area.height - 1);
+    let buffer = f.buffer_mut();
+    for x in area.x..area.x + area.width {
+        let cell = buffer.get_mut(x, y);
+        if cell.symbol() == " " {
+            cell.set_symbol("┄").set_fg(theme.muted);
+        }
+    }
+}
+
+fn draw_candles(f: &mut Frame<'_>, area: Rect, app: &App) {
+    let theme = app.theme.palette();
+    let candles = app
+        .ticker
+        .candles(CANDLE_BUCKET, (area.width as usize).div_ceil(2));
+    if candles.len() < 2 {
+        let placeholder =
+            Paragraph::new("Gathering candles...").style(Style::default().fg(theme.muted));
+        f.render_widget(placeholder, area);
+        return;
+    }
+    let high = candles
+        .iter()
+        .fold(f64::NEG_INFINITY, |acc, candle| acc.max(candle.high));
+    let low = candles
+        .iter()
+        .fold(f64::INFINITY, |acc, candle| acc.min(candle.low));
+    let rows = area.height as usize;
+    let step = (high - low).max(0.01) / rows as f64;
+
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let band_top = high - row as f64 * step;
+            let band_bottom = band_top - step;
+            let overlaps = |a: f64, b: f64| a.min(b) <= band_top && a.max(b) >= band_bottom;
+            let spans: Vec<Span> = candles
+                .iter()
+                .map(|candle| {
+                    let color = if candle.close >= candle.open {
+                        theme.positive
+                    } else {
+                        theme.negative
+                    };
+                    let glyph = if overlaps(candle.open, candle.close) {
+                        "█ "
+                    } else if overlaps(candle.low, candle.high) {
+                        "│ "
+                    } else {
+                        "  "
+                    };
+                    Span::styled(glyph, Style::default().fg(color))
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+fn build_indicator_line(app: &App) -> Line<'static> {
+    let theme = app.theme.palette();
+    let samples = app.ticker.history.len();
+    let mut spans = Vec::new();
+    for event in &app.ticker.events {
+        spans.push(Span::styled(
+            format!(
+                "⚠ {} {:.0}s",
+                event.kind.label().to_uppercase(),
+                event.remaining_secs.max(0.0)
+            ),
+            Style::default()
+                .fg(theme.background)
+                .bg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  "));
     }
+    for (label, window, color) in [
+        ("MA", SHORT_MA_WINDOW, theme.tertiary),
+        ("MA", LONG_MA_WINDOW, theme.secondary),
+    ] {
+        let value = match app.ticker.moving_average(window) {
+            Some(avg) => format!("{:.2}₵", avg),
+            None => format!("({}/{})", samples.min(window), window),
+        };
+        spans.push(Span::styled(
+            format!("{}{} {}", label, window, value),
+            Style::default().fg(color),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    let (signal, color) = match app.ticker.ma_crossover() {
+        Some(MaCrossover::Bullish) => ("MA crossover ↑", theme.positive),
+        Some(MaCrossover::Bearish) => ("MA crossover ↓", theme.negative),
+        None => match (
+            app.ticker.moving_average(SHORT_MA_WINDOW),
+            app.ticker.moving_average(LONG_MA_WINDOW),
+        ) {
+            (Some(short), Some(long)) if short > long => ("trend above", theme.label),
+            (Some(_), Some(_)) => ("trend below", theme.label),
+            _ => ("warming up", theme.muted),
+        },
+    };
+    spans.push(Span::styled(signal, Style::default().fg(color)));
+    Line::from(spans)
+}
+
+fn draw_stats_overlay(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
+    let area = centered_rect(50, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            "Statistics",
+            Style::default()
+                .fg(theme.border_focused)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let stats = &app.stats;
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Net worth ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2}₵", app.net_worth()),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "  @ {}₵/⛓, {}₵/◆",
+                format_price(app.ticker.price),
+                format_price(app.shard_ticker.price)
+            )),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Links restored ", Style::default().fg(theme.label)),
+            Span::styled(
+                stats.links_restored.to_string(),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Chain mined ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2} ⛓", stats.chain_mined),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Shards mined ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2} ◆", stats.shards_mined),
+                Style::default().fg(theme.secondary),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Credits from sales ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2}₵", stats.credits_from_sales),
+                Style::default().fg(theme.positive),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Exchange fees paid ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2}₵", app.bank.total_fees_paid),
+                Style::default().fg(theme.negative),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Legacy multiplier ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("×{:.2}", app.legacy_multiplier),
+                Style::default().fg(theme.secondary),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Session ", Style::default().fg(theme.label)),
+            Span::raw(format_duration(app.session_started.elapsed())),
+            Span::styled("  Total played ", Style::default().fg(theme.label)),
+            Span::raw(format_duration(Duration::from_secs_f64(
+                app.stats.play_time_secs,
+            ))),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{} or Esc to close", app.keymap.label(Action::ToggleStats)),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, inner);
+}
+
+fn draw_debug_overlay(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
+    let size = f.size();
+    let width = 34.min(size.width);
+    let height = 9.min(size.height);
+    let area = Rect {
+        x: size.right() - width,
+        y: size.y,
+        width,
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let remaining_work = app
+        .mining
+        .active_job
+        .as_ref()
+        .map_or(0.0, |active| active.remaining_work());
+    let seed = app
+        .seed
+        .map_or_else(|| "entropy".to_string(), |seed| seed.to_string());
+    let rows = [
+        (
+            "tick",
+            format!("{:.0}ms", app.debug.tick_interval.as_secs_f64() * 1000.0),
+        ),
+        (
+            "draw",
+            format!("{:.2}ms", app.debug.draw_time.as_secs_f64() * 1000.0),
+        ),
+        (
+            "price interval",
+            format!("{:.1}s", app.ticker.update_interval().as_secs_f64()),
+        ),
+        (
+            "next update",
+            format!("{:.1}s", app.ticker.seconds_until_update()),
+        ),
+        ("work left", format!("{:.1}", remaining_work)),
+        ("power", format_relings(app.hashpower.effective_power())),
+        ("seed", seed),
+    ];
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{:<15}", label), Style::default().fg(theme.label)),
+                Span::styled(value, Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+    let block = Block::default()
+        .title(Span::styled("Debug", Style::default().fg(theme.muted)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.muted))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_goal_editor(f: &mut Frame<'_>, app: &App) {
+    let Some(draft) = &app.goal_draft else {
+        return;
+    };
+    let theme = app.theme.palette();
+    let area = centered_rect(50, 30, f.size());
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .title("Mine until")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let current = app.goal_value(draft.metric);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", draft.metric.label()),
+                Style::default().fg(theme.label),
+            ),
+            Span::styled(
+                format!("{:.0} {}", draft.target, draft.metric.unit()),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  then {}", draft.on_reach.label()),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!(
+                "Now {:.2} {} ({:.0}%)",
+                current,
+                draft.metric.unit(),
+                draft.progress(current) * 100.0
+            ),
+            Style::default().fg(theme.muted),
+        )),
+        Line::from(""),
+        Line::from(format!(
+            "{}{} target ±{:.0}  [{}] metric  [{}] on reach",
+            app.keymap.label(Action::DraftLower),
+            app.keymap.label(Action::DraftRaise),
+            draft.metric.step(),
+            app.keymap.label(Action::ToggleGoalMetric),
+            app.keymap.label(Action::CycleGoalOnReach)
+        )),
+    ];
+    lines.push(Line::from(if app.goal.is_some() {
+        format!(
+            "Enter set  [{}] clear goal  Esc cancel",
+            app.keymap.label(Action::ClearGoal)
+        )
+    } else {
+        "Enter set  Esc cancel".to_string()
+    }));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
+fn draw_contract_details(f: &mut Frame<'_>, app: &App) {
+    let Some(job) = app.mining.available_jobs.get(app.mining.selected_job) else {
+        return;
+    };
+    let theme = app.theme.palette();
+    let area = centered_rect(60, 50, f.size());
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .title(Span::styled(
+            format!("{} · {}", job.name, job.rarity.label()),
+            rarity_style(job.rarity, theme),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let difficulties = &job.linklet_difficulties;
+    let easiest = difficulties.iter().copied().fold(f64::INFINITY, f64::min);
+    let hardest = difficulties.iter().copied().fold(0.0_f64, f64::max);
+    let mean = if difficulties.is_empty() {
+        0.0
+    } else {
+        difficulties.iter().sum::<f64>() / difficulties.len() as f64
+    };
+    let power = app.hashpower.effective_power();
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.label));
+    let lines = vec![
+        Line::from(Span::styled(
+            job.lore.clone(),
+            Style::default()
+                .fg(theme.text)
+                .add_modifier(Modifier::ITALIC),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            label("Grid "),
+            Span::raw(format!(
+                "{}×{} ({} linklets)",
+                job.rows,
+                job.cols,
+                difficulties.len()
+            )),
+        ]),
+        Line::from(vec![
+            label("Difficulty "),
+            Span::raw(format!(
+                "Δ{:.1} total  |  per linklet {:.1}–{:.1}, mean {:.1}",
+                job.difficulty, easiest, hardest, mean
+            )),
+        ]),
+        Line::from(vec![
+            label("Payout "),
+            Span::styled(
+                format!("{:.2} {}", job.payout_chain, job.asset.symbol()),
+                Style::default().fg(theme.accent),
+            ),
+            Span::raw(format!(
+                "  ≈ {:.2}₵ now  |  {:.2}₵/s",
+                job.payout_value(&app.mining.quotes),
+                job.expected_value_per_work(&app.mining.quotes) * power * app.legacy_multiplier
+            )),
+        ]),
+        Line::from(vec![
+            label("On completion "),
+            market_nudge_span(job, theme),
+            Span::styled(
+                format!("  (impact {:+.2})", job.market_impact),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(vec![
+            label("Estimate "),
+            Span::raw(format_eta(app.mining.queued_estimate_secs(job, power))),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "↑↓ browse  [{}] accept  [{}]/Esc close",
+                app.keymap.label(Action::AcceptContract),
+                app.keymap.label(Action::ContractDetails)
+            ),
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
+fn draw_purchase_confirm(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
+    let Some(pending) = app.pending_purchase else {
+        return;
+    };
+    let area = centered_rect(40, 30, f.size());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(Span::styled(
+            "Confirm Purchase",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.highlight))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let remaining = app.bank.credits_balance - pending.cost;
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Tier ", Style::default().fg(theme.label)),
+            Span::styled(
+                if pending.count > 1 {
+                    format!("{}× {}", pending.count, pending.name)
+                } else {
+                    pending.name.to_string()
+                },
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Cost ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2}₵", pending.cost),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Balance after ", Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.2}₵", remaining),
+                Style::default().fg(if remaining >= 0.0 {
+                    theme.positive
+                } else {
+                    theme.negative
+                }),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter/Y to buy · Esc/N to cancel",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, inner);
+}
+
+fn build_manipulation_line(app: &App, theme: &Theme) -> Line<'static> {
+    let keys = format!(
+        "[{}] dump all  [{}] pump all",
+        app.keymap.label(Action::Dump),
+        app.keymap.label(Action::Pump)
+    );
+    let status = if app.bank.manipulation_cooldown > 0.0 {
+        Span::styled(
+            format!(
+                "  cooling down {:.0}s",
+                app.bank.manipulation_cooldown.ceil()
+            ),
+            Style::default().fg(theme.negative),
+        )
+    } else {
+        Span::styled(
+            format!("  ready (min {:.0}₵)", MANIPULATION_MIN_VALUE),
+            Style::default().fg(theme.positive),
+        )
+    };
+    Line::from(vec![
+        Span::styled("Manipulate ", Style::default().fg(theme.label)),
+        Span::raw(keys),
+        status,
+    ])
+}
+
+fn draw_manipulation_confirm(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
+    let Some(pending) = app.pending_manipulation else {
+        return;
+    };
+    let area = centered_rect(45, 35, f.size());
+    f.render_widget(Clear, area);
+
+    let (title, verb) = match pending.side {
+        OrderSide::Sell => ("Confirm Dump", "Sell"),
+        OrderSide::Buy => ("Confirm Pump", "Buy"),
+    };
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.negative))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let price = app.ticker_for(pending.asset).price;
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{} ", verb), Style::default().fg(theme.label)),
+            Span::styled(
+                format!("{:.1} {}", pending.amount, pending.asset.symbol()),
+                Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(" filling at "),
+            Span::styled(
+                format!("{:.2}₵", pending.fill_price),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Price ", Style::default().fg(theme.label)),
+            Span::raw(format!("{:.2}₵ → ", price)),
+            Span::styled(
+                format!(
+                    "{:.2}₵ ({:+.0}%)",
+                    price * (1.0 + pending.shock),
+                    pending.shock * 100.0
+                ),
+                Style::default().fg(if pending.shock < 0.0 {
+                    theme.negative
+                } else {
+                    theme.positive
+                }),
+            ),
+        ]),
+        Line::from(format!(
+            "The market drifts back over time. Cooldown {:.0}s.",
+            MANIPULATION_COOLDOWN_SECS
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter/Y to go ahead · Esc/N to cancel",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, inner);
+}
+
+fn draw_daily_summary(f: &mut Frame<'_>, app: &App) {
+    let Some(daily) = &app.daily else {
+        return;
+    };
+    let theme = app.theme.palette();
+    let area = centered_rect(50, 40, f.size());
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .title(Span::styled(
+            format!("Daily Challenge {}", daily.date.format("%Y-%m-%d")),
+            Style::default()
+                .fg(theme.border_focused)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let verdict = match daily.outcome {
+        Some(DailyOutcome::GoalReached { secs }) => Span::styled(
+            format!(
+                "Goal reached in {}",
+                format_duration(Duration::from_secs_f64(secs))
+            ),
+            Style::default().fg(theme.positive),
+        ),
+        _ => Span::styled(
+            format!("Time up short of {:.0}₵", DAILY_GOAL_NET_WORTH),
+            Style::default().fg(theme.negative),
+        ),
+    };
+    let lines = vec![
+        Line::from(verdict),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Score ", Style::default().fg(theme.label)),
+            Span::styled(
+                daily.score.to_string(),
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Net worth ", Style::default().fg(theme.label)),
+            Span::raw(format!("{:.2}₵", app.net_worth())),
+        ]),
+        Line::from(vec![
+            Span::styled("Links restored ", Style::default().fg(theme.label)),
+            Span::raw(app.stats.links_restored.to_string()),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter/Esc/q to quit",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
+fn draw_achievements_overlay(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
+    let area = centered_rect(50, 40, f.size());
+    f.render_widget(Clear, area);
+
+    let title = format!(
+        "Achievements {}/{}",
+        app.achievements.len(),
+        AchievementId::ALL.len()
+    );
+    let block = Block::default()
+        .title(Span::styled(
+            title,
+            Style::default()
+                .fg(theme.border_focused)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
+    f.render_widget(block.clone(), area);
+    let inner = block.inner(area);
+
+    let mut lines: Vec<Line> = AchievementId::ALL
+        .iter()
+        .map(|&id| {
+            let unlocked = app.achievements.contains(&id);
+            let (mark, style) = if unlocked {
+                ("★ ", Style::default().fg(theme.highlight))
+            } else {
+                ("☆ ", Style::default().fg(theme.muted))
+            };
+            Line::from(vec![
+                Span::styled(mark, style),
+                Span::styled(id.title(), style.add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("  {}", id.description()),
+                    Style::default().fg(theme.label),
+                ),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Esc/Enter to close",
+        Style::default().fg(theme.muted),
+    )));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), inner);
+}
+
+/// Inner height of the pause overlay below which the session summary is hidden.
+const PAUSE_SUMMARY_MIN_HEIGHT: u16 = 16;
+
+/// Where the run stands, for a player coming back to a paused game.
+fn build_pause_summary_lines(app: &App, theme: &Theme) -> Vec<Line<'static>> {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(theme.label));
+    let contract = match &app.mining.active_job {
+        Some(active) => vec![
+            label("Mining "),
+            Span::styled(
+                active.job.name.clone(),
+                rarity_style(active.job.rarity, theme),
+            ),
+            Span::raw(format!(" {:.0}%", active.completion_ratio() * 100.0)),
+        ],
+        None => vec![
+            label("Mining "),
+            Span::styled("idle", Style::default().fg(theme.muted)),
+        ],
+    };
+    vec![
+        Line::from(vec![
+            label("Net worth "),
+            Span::styled(
+                format!("{:.2}₵", app.net_worth()),
+                Style::default().fg(theme.positive),
+            ),
+            label("  Credits "),
+            Span::styled(
+                format!("{:.2}₵", app.bank.credits_balance),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            label("Chain "),
+            Span::styled(
+                format!("{:.2} ⛓", app.bank.chain_balance),
+                Style::default().fg(theme.accent),
+            ),
+            label("  Power "),
+            Span::styled(
+                format_relings(app.hashpower.effective_power()),
+                Style::default().fg(theme.positive),
+            ),
+        ]),
+        Line::from(contract),
+        Line::from(vec![
+            label("Played "),
+            Span::raw(format_duration(Duration::from_secs_f64(
+                app.stats.play_time_secs,
+            ))),
+            label("  this session "),
+            Span::raw(format_duration(app.session_started.elapsed())),
+        ]),
+    ]
 }
 
 fn draw_pause_overlay(f: &mut Frame<'_>, app: &App) {
+    let theme = app.theme.palette();
     let area = centered_rect(40, 50, f.size());
     f.render_widget(Clear, area);
 
@@ -491,18 +2292,162 @@ fn draw_pause_overlay(f: &mut Frame<'_>, app: &App) {
         .title(Span::styled(
             "Paused",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.border_focused)
                 .add_modifier(Modifier::BOLD),
         ))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
-        .style(Style::default().bg(Color::Black));
+        .border_style(Style::default().fg(theme.border_focused))
+        .style(Style::default().bg(theme.background));
     f.render_widget(block.clone(), area);
     let inner = block.inner(area);
 
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(PauseConfirm::Prestige) = app.pause_menu.confirm() {
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "Prestige {:.2}₵ net worth? Bank, rigs, contracts and ledger reset.",
+                app.net_worth()
+            ),
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(vec![
+            Span::raw("Legacy multiplier "),
+            Span::styled(
+                format!("×{:.2}", app.legacy_multiplier),
+                Style::default().fg(theme.label),
+            ),
+            Span::raw(" → "),
+            Span::styled(
+                format!("×{:.2}", app.projected_legacy_multiplier()),
+                Style::default().fg(theme.positive),
+            ),
+        ]));
+        lines.push(Line::from("Y/Enter confirm  N/Esc cancel"));
+        lines.push(Line::from(""));
+    }
+    if let Some(PauseConfirm::SaveBeforeExit) = app.pause_menu.confirm() {
+        lines.push(Line::from(vec![Span::styled(
+            "Save before exiting?",
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from("Y/Enter save & exit  N exit without saving"));
+        lines.push(Line::from("Esc stay"));
+        lines.push(Line::from(""));
+    }
+    if let Some(PauseConfirm::SaveBeforeNewGame) = app.pause_menu.confirm() {
+        lines.push(Line::from(vec![Span::styled(
+            "Save before starting a new game?",
+            Style::default()
+                .fg(theme.negative)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.push(Line::from(
+            "Y/Enter save & restart  N restart without saving",
+        ));
+        lines.push(Line::from("Esc stay"));
+        lines.push(Line::from(""));
+    }
+    if let Some(status) = app.pause_menu.status() {
+        lines.push(Line::from(vec![Span::styled(
+            status.clone(),
+            Style::default().fg(theme.accent),
+        )]));
+        lines.push(Line::from(""));
+    }
+    // Confirm prompts and status sit above the key help and must stay readable.
+    let width = inner.width.max(1) as usize;
+    let prompt_rows: usize = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(width))
+        .sum();
+    lines.push(Line::from("↑↓ select  Enter confirm  Esc resume"));
+    lines.push(Line::from(format!(
+        "{}/{}/{}/{} jump to Mining/Hashpower/Bank/Ledger  {} cycle panes",
+        app.keymap.label(Action::FocusMining),
+        app.keymap.label(Action::FocusHashpower),
+        app.keymap.label(Action::FocusBank),
+        app.keymap.label(Action::FocusLedger),
+        app.keymap.label(Action::NextPane)
+    )));
+    lines.push(Line::from(""));
+    let keys = &app.keymap;
+    let nav = format!(
+        "{}{}",
+        keys.label(Action::MoveUp),
+        keys.label(Action::MoveDown)
+    );
+    lines.push(Line::from(format!(
+        "{} cycle focus  |  {} pause  |  {} stats  |  {} zoom  |  {} undo trade/purchase  |  {} line/candle chart  |  {} chart scale  |  {} snapshot",
+        keys.label(Action::NextPane),
+        keys.label(Action::Pause),
+        keys.label(Action::ToggleStats),
+        keys.label(Action::ToggleZoom),
+        keys.label(Action::Undo),
+        keys.label(Action::ToggleChart),
+        keys.label(Action::ToggleChartScale),
+        keys.label(Action::Snapshot)
+    )));
+    lines.push(Line::from(format!(
+        "Mining: {} select  {} accept  Ctrl+{} reroll  {} auto-accept  {} sort  {} work strategy",
+        nav,
+        keys.label(Action::AcceptContract),
+        keys.label(Action::RerollContracts),
+        keys.label(Action::ToggleAutoAccept),
+        keys.label(Action::CycleSort),
+        keys.label(Action::ToggleStrategy)
+    )));
+    lines.push(Line::from(format!(
+        "Hashpower: {} focus tier  {} purchase  {} buy ×10  {} buy max  {} overclock  {} research",
+        nav,
+        keys.label(Action::PurchaseTier),
+        keys.label(Action::PurchaseTen),
+        keys.label(Action::PurchaseMax),
+        keys.label(Action::Overclock),
+        keys.label(Action::ToggleResearch)
+    )));
+    lines.push(Line::from(format!(
+        "Bank: {} sell  {} buy  {} bulk buy  {} bulk sell  {} switch asset  {} dump  {} pump  {} limit order  {} cancel order  {} alert  {} clear alerts",
+        keys.label(Action::SellChain),
+        keys.label(Action::BuyChain),
+        keys.label(Action::BuyBulk),
+        keys.label(Action::SellBulk),
+        keys.label(Action::SwitchAsset),
+        keys.label(Action::Dump),
+        keys.label(Action::Pump),
+        keys.label(Action::NewLimitOrder),
+        keys.label(Action::CancelLimitOrder),
+        keys.label(Action::NewAlert),
+        keys.label(Action::ClearAlerts)
+    )));
+    lines.push(Line::from(format!(
+        "Ledger: {} scroll  {}/{} page  {}/{} newest/oldest  {} search (Esc clears)  {} copy link ID",
+        nav,
+        keys.label(Action::PageUp),
+        keys.label(Action::PageDown),
+        keys.label(Action::JumpNewest),
+        keys.label(Action::JumpOldest),
+        keys.label(Action::SearchLedger),
+        keys.label(Action::CopyLinkId)
+    )));
+
+    let summary = build_pause_summary_lines(app, theme);
+    // The menu and help keep priority; the summary drops out on short terminals.
+    let summary_height = if inner.height >= PAUSE_SUMMARY_MIN_HEIGHT {
+        summary.len() as u16 + 1
+    } else {
+        0
+    };
     let layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(summary_height),
+            Constraint::Length((prompt_rows as u16).max(4)),
+        ])
         .split(inner);
 
     let items: Vec<ListItem> = app
@@ -510,40 +2455,84 @@ fn draw_pause_overlay(f: &mut Frame<'_>, app: &App) {
         .items()
         .iter()
         .map(|item| {
-            ListItem::new(Line::from(vec![Span::styled(
-                item.label(),
-                Style::default().fg(Color::White),
-            )]))
+            let mut spans = vec![Span::styled(item.label(), Style::default().fg(theme.text))];
+            if let PauseMenuItem::Autosave = item {
+                let interval = if app.autosave_interval.is_zero() {
+                    "off".to_string()
+                } else {
+                    format!("every {}s", app.autosave_interval.as_secs())
+                };
+                spans.push(Span::styled(
+                    format!(": {}", interval),
+                    Style::default().fg(theme.label),
+                ));
+            }
+            if let PauseMenuItem::FocusPause = item {
+                let state = if app.pause_on_focus_loss { "on" } else { "off" };
+                spans.push(Span::styled(
+                    format!(": {}", state),
+                    Style::default().fg(theme.label),
+                ));
+            }
+            if let PauseMenuItem::Glyphs = item {
+                spans.push(Span::styled(
+                    format!(": {}", app.grid_glyphs.label()),
+                    Style::default().fg(theme.label),
+                ));
+            }
+            if let PauseMenuItem::Theme = item {
+                spans.push(Span::styled(
+                    format!(": {}", app.theme.label()),
+                    Style::default().fg(theme.label),
+                ));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
     let mut state = ListState::default();
     state.select(Some(app.pause_menu.selected()));
     let list = List::new(items).block(Block::default()).highlight_style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(theme.highlight)
             .add_modifier(Modifier::BOLD),
     );
     f.render_stateful_widget(list, layout[0], &mut state);
 
-    let mut lines: Vec<Line> = Vec::new();
-    if let Some(status) = app.pause_menu.status() {
-        lines.push(Line::from(vec![Span::styled(
-            status.clone(),
-            Style::default().fg(Color::LightCyan),
-        )]));
-        lines.push(Line::from(""));
+    if summary_height > 0 {
+        let block = Block::default()
+            .title(Span::styled("Session", Style::default().fg(theme.label)))
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.muted));
+        f.render_widget(Paragraph::new(summary).block(block), layout[1]);
     }
-    lines.push(Line::from("↑↓ select  Enter confirm  Esc resume"));
-    lines.push(Line::from(""));
-    lines.push(Line::from("Tab cycle focus  |  Q pause"));
-    lines.push(Line::from("Mining: ↑↓ select  Enter accept  Ctrl+R reroll"));
-    lines.push(Line::from("Hashpower: ↑↓ focus tier  Enter purchase"));
-    lines.push(Line::from("Bank: ← sell  → buy  B bulk buy  M bulk sell"));
-    lines.push(Line::from("Ledger: ↑↓ scroll"));
+
     let status = Paragraph::new(lines)
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
-    f.render_widget(status, layout[1]);
+    f.render_widget(status, layout[2]);
+}
+
+fn draw_too_small(f: &mut Frame<'_>, size: Rect, theme: &Theme) {
+    let area = centered_rect(100, 50, size);
+    let lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "Terminal too small — resize to at least {}×{}",
+                MIN_WIDTH, MIN_HEIGHT
+            ),
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("Current size {}×{}", size.width, size.height),
+            Style::default().fg(theme.label),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
@@ -555,27 +2544,93 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
             Constraint::Percentage((100 - percent_y) / 2),
         ])
         .split(area);
-    let horizontal = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(vertical[1]);
-    horizontal[1]
+    let row = vertical[1];
+    // Popups keep a usable width on narrow terminals instead of shrinking with them.
+    let width = ((u32::from(row.width) * u32::from(percent_x) / 100) as u16)
+        .max(POPUP_MIN_WIDTH)
+        .min(row.width);
+    Rect {
+        x: row.x + (row.width - width) / 2,
+        width,
+        ..row
+    }
 }
 
-fn pane_block<'a>(title: &'a str, focused: bool) -> Block<'a> {
+fn pane_block<'a>(title: &'a str, focused: bool, theme: &Theme) -> Block<'a> {
     let border_style = if focused {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(theme.border_focused)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.border)
     };
     Block::default()
-        .title(Span::styled(title, Style::default().fg(Color::White)))
+        .title(Span::styled(title, Style::default().fg(theme.text)))
         .borders(Borders::ALL)
         .border_style(border_style)
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::app::test_app;
+
+    fn plan(width: u16, height: u16) -> LayoutPlan {
+        layout_plan(Rect::new(0, 0, width, height))
+    }
+
+    #[test]
+    fn panes_stack_below_one_hundred_columns() {
+        assert!(matches!(plan(99, 40), LayoutPlan::Stacked { .. }));
+        assert_eq!(plan(100, 40), LayoutPlan::Columns);
+    }
+
+    #[test]
+    fn narrow_terminals_down_to_the_minimum_stack() {
+        assert!(matches!(
+            plan(MIN_WIDTH, MIN_HEIGHT),
+            LayoutPlan::Stacked { .. }
+        ));
+        assert!(matches!(plan(60, 30), LayoutPlan::Stacked { .. }));
+    }
+
+    fn render(app: &App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| draw(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn every_pane_renders_at_the_minimum_width() {
+        let mut app = test_app(3, Preset::Normal);
+        for focus in [
+            PaneFocus::Mining,
+            PaneFocus::Hashpower,
+            PaneFocus::Bank,
+            PaneFocus::Ledger,
+        ] {
+            app.focus = focus;
+            app.zoom = None;
+            assert!(!render(&app, MIN_WIDTH, MIN_HEIGHT).contains("too small"));
+            app.zoom = Some(focus);
+            assert!(!render(&app, MIN_WIDTH, MIN_HEIGHT).contains("too small"));
+        }
+        app.paused = true;
+        assert!(render(&app, MIN_WIDTH, MIN_HEIGHT).contains("Resume"));
+        assert!(render(&app, MIN_WIDTH - 1, MIN_HEIGHT).contains("too small"));
+    }
+
+    #[test]
+    fn collapsed_panes_scale_with_height_down_to_a_floor() {
+        assert_eq!(plan(90, 48), LayoutPlan::Stacked { collapsed_rows: 6 });
+        assert_eq!(
+            plan(90, MIN_HEIGHT),
+            LayoutPlan::Stacked { collapsed_rows: 3 }
+        );
+        assert_eq!(plan(90, 12), LayoutPlan::Stacked { collapsed_rows: 3 });
+    }
+}